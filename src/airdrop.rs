@@ -0,0 +1,169 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use multiversx_sc_modules::ongoing_operation::{
+    CONTINUE_OP, DEFAULT_MIN_GAS_TO_SAVE_PROGRESS, STOP_OP,
+};
+
+use crate::contexts::base::StorageCache;
+use crate::errors::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct AirdropEntry<M: ManagedTypeApi> {
+    pub beneficiary: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait AirdropModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::liquidity_pool::LiquidityPoolModule
+    + crate::stats::StatsModule
+    + crate::events::EventsModule
+    + crate::reports::ReportsModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[payable("*")]
+    #[endpoint(addAirdropEntries)]
+    fn add_airdrop_entries(
+        &self,
+        entries: MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>>,
+    ) {
+        self.require_owner();
+        let payment = self.call_value().single_esdt();
+        require!(!self.is_emergency_mode(), ERROR_EMERGENCY_MODE);
+        require!(
+            payment.token_identifier == self.main_token().get(),
+            ERROR_WRONG_TOKEN
+        );
+        require!(!entries.is_empty(), ERROR_NO_RECIPIENTS);
+
+        let mut entries_mapper = self.airdrop_entries();
+        let mut total_amount = BigUint::zero();
+        for entry in entries.into_iter() {
+            let (beneficiary, amount) = entry.into_tuple();
+            require!(amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
+
+            total_amount += &amount;
+            entries_mapper.push(&AirdropEntry {
+                beneficiary,
+                amount,
+            });
+        }
+
+        require!(
+            total_amount == payment.amount,
+            ERROR_AIRDROP_AMOUNT_MISMATCH
+        );
+
+        self.airdrop_pending_amount()
+            .update(|pending| *pending += total_amount);
+    }
+
+    #[endpoint(processAirdrop)]
+    fn process_airdrop(&self) -> OperationCompletionStatus {
+        self.require_owner();
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.is_state_active(storage_cache.contract_state()),
+            ERROR_NOT_ACTIVE
+        );
+        require!(!self.airdrop_entries().is_empty(), ERROR_NO_AIRDROP_ENTRIES);
+
+        // Every entry is removed as soon as it is delegated, the call must be repeated until it returns completed
+        self.run_while_it_has_gas(DEFAULT_MIN_GAS_TO_SAVE_PROGRESS, || {
+            match self.pop_airdrop_entry() {
+                Some(entry) => {
+                    self.delegate_airdrop_entry(&entry, &mut storage_cache);
+                    CONTINUE_OP
+                }
+                None => STOP_OP,
+            }
+        })
+    }
+
+    // Returns the queued entries to the owner, for instance when one of them no longer fits the caps
+    // Works in emergency mode as well, the call must be repeated until it returns completed
+    #[endpoint(cancelAirdrop)]
+    fn cancel_airdrop(&self) -> OperationCompletionStatus {
+        self.require_owner();
+        require!(!self.airdrop_entries().is_empty(), ERROR_NO_AIRDROP_ENTRIES);
+
+        let mut refund_amount = BigUint::zero();
+        let run_result = self.run_while_it_has_gas(DEFAULT_MIN_GAS_TO_SAVE_PROGRESS, || match self
+            .pop_airdrop_entry()
+        {
+            Some(entry) => {
+                refund_amount += entry.amount;
+                CONTINUE_OP
+            }
+            None => STOP_OP,
+        });
+
+        self.airdrop_pending_amount()
+            .update(|pending| *pending -= &refund_amount);
+        self.tx()
+            .to(ToCaller)
+            .single_esdt(&self.main_token().get(), 0, &refund_amount)
+            .transfer();
+
+        run_result
+    }
+
+    // Entries are taken from the end, so removing one never moves the others
+    fn pop_airdrop_entry(&self) -> Option<AirdropEntry<Self::Api>> {
+        let mut entries_mapper = self.airdrop_entries();
+        let last_index = entries_mapper.len();
+        if last_index == 0 {
+            return None;
+        }
+
+        let entry = entries_mapper.get(last_index);
+        entries_mapper.swap_remove(last_index);
+        Some(entry)
+    }
+
+    fn delegate_airdrop_entry(
+        &self,
+        entry: &AirdropEntry<Self::Api>,
+        storage_cache: &mut StorageCache<Self>,
+    ) {
+        self.track_beneficiary_delegation(&entry.beneficiary, &entry.amount);
+
+        let user_payment = self.process_delegation(&entry.amount, storage_cache);
+        self.tx()
+            .to(&entry.beneficiary)
+            .payment(&user_payment)
+            .transfer();
+
+        self.airdrop_pending_amount()
+            .update(|pending| *pending -= &entry.amount);
+
+        self.emit_delegate_event(
+            storage_cache,
+            &entry.beneficiary,
+            &user_payment.amount,
+            &entry.amount,
+        );
+    }
+
+    // Entries still to be delegated and their main token amount
+    #[view(getAirdropProgress)]
+    fn get_airdrop_progress(&self) -> MultiValue2<usize, BigUint> {
+        (
+            self.airdrop_entries().len(),
+            self.airdrop_pending_amount().get(),
+        )
+            .into()
+    }
+
+    #[storage_mapper("airdropEntries")]
+    fn airdrop_entries(&self) -> VecMapper<AirdropEntry<Self::Api>>;
+
+    #[view(getAirdropPendingAmount)]
+    #[storage_mapper("airdropPendingAmount")]
+    fn airdrop_pending_amount(&self) -> SingleValueMapper<BigUint>;
+}
@@ -1,25 +1,294 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::liquidity_pool::State;
+use multiversx_sc::abi::{
+    StructFieldDescription, TypeAbiFrom, TypeContents, TypeDescription, TypeDescriptionContainer,
+    TypeName,
+};
 
-pub const UNBOND_PERIOD: u64 = 10;
+use crate::errors::*;
+use crate::liquidity_pool::{State, UNDELEGATE_TOKEN_URI};
+
+pub const DEFAULT_UNBOND_TIER: u8 = 0;
+pub const DEFAULT_UNBOND_PERIOD: u64 = 10;
 pub const INITIAL_EXCHANGE_RATE: u64 = 1_000_000_000_000_000_000;
+pub const MAX_PERCENTAGE: u64 = 10_000;
+pub const DEFAULT_MAX_LOSS_PER_CALL: u64 = 1_000;
+pub const MAX_NAME_TEMPLATE_LENGTH: usize = 100;
+pub const MAX_EXCHANGE_RATE_DECIMALS: u32 = 36;
+pub const DEFAULT_UNSTAKE_TOKEN_NAME_TEMPLATE: &[u8] = b"UXOXNO {amount} unlocks at epoch {epoch}";
+pub const DEFAULT_EPOCH_UNSTAKE_TOKEN_NAME_TEMPLATE: &[u8] = b"UXOXNO unlocks at epoch {epoch}";
+
+// Attributes minted before the version byte start with the big endian length of original_amount, so their first byte is zero
+pub const UNVERSIONED_ATTRIBUTES_TAG: u8 = 0;
+pub const UNSTAKE_TOKEN_ATTRIBUTES_VERSION: u8 = 1;
+
+// Wire format: the version byte, then every field nested encoded in declaration order
+// The version is not a field, the TypeAbi implementation below adds it so the ABI matches the bytes on chain
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnstakeTokenAttributes<M: ManagedTypeApi> {
+    pub original_amount: BigUint<M>,
+    pub share_amount: BigUint<M>,
+    pub unstake_epoch: u64,
+    pub unbond_epoch: u64,
+    pub tier_id: u8,
+    pub unstake_value_index: BigUint<M>,
+}
+
+// Every layout the unstake token attributes were minted with, new fields go into a new version
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UnstakeTokenAttributesLayout<M: ManagedTypeApi> {
+    // The first four fields, optionally followed by the tier and then by the unstake value index
+    Unversioned(UnstakeTokenAttributes<M>),
+    V1(UnstakeTokenAttributes<M>),
+}
+
+impl<M: ManagedTypeApi> UnstakeTokenAttributesLayout<M> {
+    pub fn version(&self) -> u8 {
+        match self {
+            UnstakeTokenAttributesLayout::Unversioned(_) => UNVERSIONED_ATTRIBUTES_TAG,
+            UnstakeTokenAttributesLayout::V1(_) => UNSTAKE_TOKEN_ATTRIBUTES_VERSION,
+        }
+    }
+
+    pub fn into_attributes(self) -> UnstakeTokenAttributes<M> {
+        match self {
+            UnstakeTokenAttributesLayout::Unversioned(attributes)
+            | UnstakeTokenAttributesLayout::V1(attributes) => attributes,
+        }
+    }
+
+    fn decode_unversioned<I, H>(input: &mut I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: codec::NestedDecodeInput,
+        H: codec::DecodeErrorHandler,
+    {
+        let original_amount = BigUint::dep_decode_or_handle_err(input, h)?;
+        let share_amount = BigUint::dep_decode_or_handle_err(input, h)?;
+        let unstake_epoch = u64::dep_decode_or_handle_err(input, h)?;
+        let unbond_epoch = u64::dep_decode_or_handle_err(input, h)?;
+
+        // Attributes minted before the unbond tiers were introduced end here and used the default tier
+        let tier_id = if input.is_depleted() {
+            DEFAULT_UNBOND_TIER
+        } else {
+            u8::dep_decode_or_handle_err(input, h)?
+        };
+
+        // Attributes minted before losses could be applied were never subject to a haircut
+        let unstake_value_index = if input.is_depleted() {
+            BigUint::from(INITIAL_EXCHANGE_RATE)
+        } else {
+            BigUint::dep_decode_or_handle_err(input, h)?
+        };
+
+        Ok(UnstakeTokenAttributesLayout::Unversioned(
+            UnstakeTokenAttributes {
+                original_amount,
+                share_amount,
+                unstake_epoch,
+                unbond_epoch,
+                tier_id,
+                unstake_value_index,
+            },
+        ))
+    }
+
+    fn decode_v1<I, H>(input: &mut I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: codec::NestedDecodeInput,
+        H: codec::DecodeErrorHandler,
+    {
+        Ok(UnstakeTokenAttributesLayout::V1(UnstakeTokenAttributes {
+            original_amount: BigUint::dep_decode_or_handle_err(input, h)?,
+            share_amount: BigUint::dep_decode_or_handle_err(input, h)?,
+            unstake_epoch: u64::dep_decode_or_handle_err(input, h)?,
+            unbond_epoch: u64::dep_decode_or_handle_err(input, h)?,
+            tier_id: u8::dep_decode_or_handle_err(input, h)?,
+            unstake_value_index: BigUint::dep_decode_or_handle_err(input, h)?,
+        }))
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for UnstakeTokenAttributesLayout<M> {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: codec::TopDecodeInput,
+        H: codec::DecodeErrorHandler,
+    {
+        let buffer = ManagedBuffer::<M>::top_decode_or_handle_err(input, h)?;
+        let mut version = [0u8; 1];
+        if buffer.load_slice(0, &mut version).is_err() {
+            return Err(h.handle_error(codec::DecodeError::INPUT_TOO_SHORT));
+        }
+
+        let mut nested_buffer = ManagedBufferNestedDecodeInput::new(buffer);
+        let layout = if version[0] == UNVERSIONED_ATTRIBUTES_TAG {
+            Self::decode_unversioned(&mut nested_buffer, h)?
+        } else {
+            match u8::dep_decode_or_handle_err(&mut nested_buffer, h)? {
+                UNSTAKE_TOKEN_ATTRIBUTES_VERSION => Self::decode_v1(&mut nested_buffer, h)?,
+                _ => return Err(h.handle_error(codec::DecodeError::INVALID_VALUE)),
+            }
+        };
+
+        if !codec::NestedDecodeInput::is_depleted(&nested_buffer) {
+            return Err(h.handle_error(codec::DecodeError::INPUT_TOO_LONG));
+        }
+
+        Ok(layout)
+    }
+}
+
+// Always written with the latest layout, any previous layout is accepted when decoding
+impl<M: ManagedTypeApi> TopEncode for UnstakeTokenAttributes<M> {
+    fn top_encode_or_handle_err<O, H>(&self, output: O, h: H) -> Result<(), H::HandledErr>
+    where
+        O: codec::TopEncodeOutput,
+        H: codec::EncodeErrorHandler,
+    {
+        let mut nested_buffer = output.start_nested_encode();
+        UNSTAKE_TOKEN_ATTRIBUTES_VERSION.dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.original_amount
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.share_amount
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.unstake_epoch
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.unbond_epoch
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.tier_id
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        self.unstake_value_index
+            .dep_encode_or_handle_err(&mut nested_buffer, h)?;
+        output.finalize_nested_encode(nested_buffer);
+        Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for UnstakeTokenAttributes<M> {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: codec::TopDecodeInput,
+        H: codec::DecodeErrorHandler,
+    {
+        UnstakeTokenAttributesLayout::top_decode_or_handle_err(input, h)
+            .map(UnstakeTokenAttributesLayout::into_attributes)
+    }
+}
 
+// Written by hand instead of #[type_abi] to describe the leading version byte
+impl<M: ManagedTypeApi> TypeAbiFrom<Self> for UnstakeTokenAttributes<M> {}
+impl<M: ManagedTypeApi> TypeAbiFrom<&Self> for UnstakeTokenAttributes<M> {}
+
+impl<M: ManagedTypeApi> TypeAbi for UnstakeTokenAttributes<M> {
+    type Unmanaged = Self;
+
+    fn type_name() -> TypeName {
+        "UnstakeTokenAttributes".into()
+    }
+
+    fn provide_type_descriptions<TDC: TypeDescriptionContainer>(accumulator: &mut TDC) {
+        let type_names = Self::type_names();
+        if accumulator.contains_type(&type_names.abi) {
+            return;
+        }
+
+        accumulator.reserve_type_name(type_names.clone());
+        let field_descriptions = multiversx_sc::types::heap::Vec::from([
+            StructFieldDescription::new(
+                &["Layout version, currently 1. Tokens minted before the version existed start with 0 instead, the first byte of the original_amount length, and may lack tier_id and unstake_value_index."],
+                "version",
+                u8::type_names(),
+            ),
+            StructFieldDescription::new(&[], "original_amount", BigUint::<M>::type_names()),
+            StructFieldDescription::new(&[], "share_amount", BigUint::<M>::type_names()),
+            StructFieldDescription::new(&[], "unstake_epoch", u64::type_names()),
+            StructFieldDescription::new(&[], "unbond_epoch", u64::type_names()),
+            StructFieldDescription::new(&[], "tier_id", u8::type_names()),
+            StructFieldDescription::new(&[], "unstake_value_index", BigUint::<M>::type_names()),
+        ]);
+        u8::provide_type_descriptions(accumulator);
+        BigUint::<M>::provide_type_descriptions(accumulator);
+        u64::provide_type_descriptions(accumulator);
+        accumulator.insert(
+            type_names.clone(),
+            TypeDescription::new(
+                &["Unstake token attributes as written by the latest version of the contract."],
+                type_names,
+                TypeContents::Struct(field_descriptions),
+                &["TopEncode", "TopDecode"],
+            ),
+        );
+    }
+}
+
+// Chosen when the unstake token is issued, since the token type cannot change afterwards
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnstakeTokenMode {
+    // One NFT for every unstake, also the mode of contracts issued before the modes existed
+    PerPosition,
+    // One SFT nonce for every unbond epoch, the quantity is the unstaked XOXNO amount
+    PerEpoch,
+}
+
+// Latest per-epoch nonce, a loss in between starts a new nonce with the new value index
+#[type_abi]
+#[derive(TopEncode, TopDecode, Clone, PartialEq, Eq, Debug)]
+pub struct EpochUnstakeNonce<M: ManagedTypeApi> {
+    pub nonce: u64,
+    pub unstake_value_index: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnbondTier {
+    pub unbond_epochs: u64,
+    pub fee: u64,
+}
+
+// Everything a front-end needs to render the pool, in a single query
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
-pub struct UnstakeTokenAttributes<M: ManagedTypeApi> {
+pub struct ContractSummary<M: ManagedTypeApi> {
+    pub state: State,
+    pub main_token_id: TokenIdentifier<M>,
+    pub ls_token_id: Option<TokenIdentifier<M>>,
+    pub unstake_token_id: Option<TokenIdentifier<M>>,
+    pub unstake_token_mode: UnstakeTokenMode,
+    pub ls_token_supply: BigUint<M>,
+    pub virtual_xoxno_reserve: BigUint<M>,
+    pub unstake_token_supply: BigUint<M>,
+    pub rounding_dust: BigUint<M>,
+    pub exchange_rate: BigUint<M>,
+    pub unbond_period: u64, // Unbond epochs of the default tier
+    pub current_epoch: u64,
+    pub contract_balance: BigUint<M>, // Main token balance, including the pending unstakes and airdrops
+}
+
+// Unstaked position behind a quantity of an unstake token, per-epoch tokens split the shares of their nonce pro-rata
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct UnstakePositionInfo<M: ManagedTypeApi> {
+    pub nonce: u64,
     pub original_amount: BigUint<M>,
     pub share_amount: BigUint<M>,
+    pub current_value: BigUint<M>, // Original amount after the losses applied since the unstake
+    pub haircut_amount: BigUint<M>,
+    pub haircut: u64, // Share of the original amount lost, in basis points of MAX_PERCENTAGE
     pub unstake_epoch: u64,
     pub unbond_epoch: u64,
+    pub epochs_remaining: u64,
+    pub tier_id: u8,
+    pub withdrawable: bool, // The unbond epoch was reached and the contract is active
 }
 
 #[multiversx_sc::module]
 pub trait ConfigModule:
-    multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    crate::ownership::OwnershipModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
 {
-    #[only_owner]
     #[payable("EGLD")]
     #[endpoint(registerLsToken)]
     fn register_ls_token(
@@ -28,6 +297,7 @@ pub trait ConfigModule:
         token_ticker: ManagedBuffer,
         num_decimals: usize,
     ) {
+        self.require_owner();
         let payment_amount = self.call_value().egld().clone_value();
         self.ls_token().issue_and_set_all_roles(
             payment_amount,
@@ -38,7 +308,6 @@ pub trait ConfigModule:
         );
     }
 
-    #[only_owner]
     #[payable("EGLD")]
     #[endpoint(registerUnstakeToken)]
     fn register_unstake_token(
@@ -46,10 +315,19 @@ pub trait ConfigModule:
         token_display_name: ManagedBuffer,
         token_ticker: ManagedBuffer,
         num_decimals: usize,
+        mode: OptionalValue<UnstakeTokenMode>,
     ) {
+        self.require_owner();
         let payment_amount = self.call_value().egld().clone_value();
+        let mode = mode.into_option().unwrap_or(UnstakeTokenMode::PerPosition);
+        let token_type = match mode {
+            UnstakeTokenMode::PerPosition => EsdtTokenType::NonFungible,
+            UnstakeTokenMode::PerEpoch => EsdtTokenType::SemiFungible,
+        };
+
+        self.unstake_token_mode().set(mode);
         self.unstake_token().issue_and_set_all_roles(
-            EsdtTokenType::NonFungible,
+            token_type,
             payment_amount,
             token_display_name,
             token_ticker,
@@ -58,23 +336,251 @@ pub trait ConfigModule:
         );
     }
 
-    #[only_owner]
     #[endpoint(setStateActive)]
     fn set_state_active(&self) {
+        self.require_owner();
+        require!(!self.is_emergency_mode(), ERROR_EMERGENCY_MODE);
         self.state().set(State::Active);
     }
 
-    #[only_owner]
     #[endpoint(setStateInactive)]
     fn set_state_inactive(&self) {
+        self.require_owner();
+        require!(!self.is_emergency_mode(), ERROR_EMERGENCY_MODE);
         self.state().set(State::Inactive);
     }
 
+    #[endpoint(setEarlyWithdrawMaxPenalty)]
+    fn set_early_withdraw_max_penalty(&self, max_penalty: u64) {
+        self.require_owner();
+        require!(max_penalty <= MAX_PERCENTAGE, ERROR_INVALID_PERCENTAGE);
+        self.early_withdraw_max_penalty().set(max_penalty);
+    }
+
+    #[endpoint(setUnbondTier)]
+    fn set_unbond_tier(&self, tier_id: u8, unbond_epochs: u64, fee: u64) {
+        self.require_owner();
+        require!(fee <= MAX_PERCENTAGE, ERROR_INVALID_PERCENTAGE);
+        self.unbond_tiers()
+            .insert(tier_id, UnbondTier { unbond_epochs, fee });
+    }
+
+    #[endpoint(removeUnbondTier)]
+    fn remove_unbond_tier(&self, tier_id: u8) {
+        self.require_owner();
+        require!(
+            self.unbond_tiers().remove(&tier_id).is_some(),
+            ERROR_UNKNOWN_UNBOND_TIER
+        );
+    }
+
+    // Zero removes the limit
+    #[endpoint(setMaxUnbondPerEpoch)]
+    fn set_max_unbond_per_epoch(&self, max_amount: BigUint) {
+        self.require_owner();
+        if max_amount == 0 {
+            self.max_unbond_per_epoch().clear();
+        } else {
+            self.max_unbond_per_epoch().set(max_amount);
+        }
+
+        // A higher limit opens epochs the stored cursors jump over, so they all start over
+        self.unbond_cursor_generation()
+            .update(|generation| *generation += 1);
+    }
+
+    // Zero removes the cap
+    #[endpoint(setMaxTotalReserve)]
+    fn set_max_total_reserve(&self, max_amount: BigUint) {
+        self.require_owner();
+        if max_amount == 0 {
+            self.max_total_reserve().clear();
+        } else {
+            self.max_total_reserve().set(max_amount);
+        }
+    }
+
+    // Zero removes the cap
+    #[endpoint(setMaxDelegationPerAddress)]
+    fn set_max_delegation_per_address(&self, max_amount: BigUint) {
+        self.require_owner();
+        if max_amount == 0 {
+            self.max_delegation_per_address().clear();
+        } else {
+            self.max_delegation_per_address().set(max_amount);
+        }
+    }
+
+    #[endpoint(setMinDelegateAmount)]
+    fn set_min_delegate_amount(&self, min_amount: BigUint) {
+        self.require_owner();
+        self.min_delegate_amount().set(min_amount);
+    }
+
+    #[endpoint(setMinUnDelegateAmount)]
+    fn set_min_undelegate_amount(&self, min_amount: BigUint) {
+        self.require_owner();
+        self.min_undelegate_amount().set(min_amount);
+    }
+
+    #[endpoint(setInstantWithdrawThreshold)]
+    fn set_instant_withdraw_threshold(&self, threshold: BigUint) {
+        self.require_owner();
+        self.instant_withdraw_threshold().set(threshold);
+    }
+
+    #[endpoint(setMaxLossPerCall)]
+    fn set_max_loss_per_call(&self, max_loss: u64) {
+        self.require_owner();
+        require!(max_loss < MAX_PERCENTAGE, ERROR_INVALID_PERCENTAGE);
+        self.max_loss_per_call().set(max_loss);
+    }
+
+    // Receives the swept rounding dust
+    #[endpoint(setTreasury)]
+    fn set_treasury(&self, treasury: ManagedAddress) {
+        self.require_owner();
+        require!(!treasury.is_zero(), ERROR_INVALID_ADDRESS);
+        self.treasury().set(treasury);
+    }
+
+    // An empty list restores the default URI
+    #[endpoint(setUnstakeTokenUris)]
+    fn set_unstake_token_uris(&self, uris: MultiValueEncoded<ManagedBuffer>) {
+        self.require_owner();
+        if uris.is_empty() {
+            self.unstake_token_uris().clear();
+        } else {
+            self.unstake_token_uris().set(uris.to_vec());
+        }
+    }
+
+    // {amount} and {epoch} are replaced with the unstaked amount and the unbond epoch, empty restores the default
+    // The amount is zero for per-epoch tokens, their quantity carries it
+    #[endpoint(setUnstakeTokenNameTemplate)]
+    fn set_unstake_token_name_template(&self, template: ManagedBuffer) {
+        self.require_owner();
+        require!(
+            template.len() <= MAX_NAME_TEMPLATE_LENGTH,
+            ERROR_INVALID_NAME_TEMPLATE
+        );
+        if template.is_empty() {
+            self.unstake_token_name_template().clear();
+        } else {
+            self.unstake_token_name_template().set(template);
+        }
+    }
+
+    #[view(getUnstakeTokenUris)]
+    fn get_unstake_token_uris(&self) -> ManagedVec<ManagedBuffer> {
+        if self.unstake_token_uris().is_empty() {
+            ManagedVec::from_single_item(ManagedBuffer::from(UNDELEGATE_TOKEN_URI))
+        } else {
+            self.unstake_token_uris().get()
+        }
+    }
+
+    #[view(getUnstakeTokenNameTemplate)]
+    fn get_unstake_token_name_template(&self) -> ManagedBuffer {
+        if self.unstake_token_name_template().is_empty() {
+            match self.unstake_token_mode().get() {
+                UnstakeTokenMode::PerPosition => {
+                    ManagedBuffer::from(DEFAULT_UNSTAKE_TOKEN_NAME_TEMPLATE)
+                }
+                UnstakeTokenMode::PerEpoch => {
+                    ManagedBuffer::from(DEFAULT_EPOCH_UNSTAKE_TOKEN_NAME_TEMPLATE)
+                }
+            }
+        } else {
+            self.unstake_token_name_template().get()
+        }
+    }
+
+    // Zero when no per-epoch token was minted for the unbond epoch, unstake epoch and tier
+    #[view(getEpochUnstakeNonce)]
+    fn get_epoch_unstake_nonce(&self, unbond_epoch: u64, unstake_epoch: u64, tier_id: u8) -> u64 {
+        let epoch_nonce_mapper = self.epoch_unstake_nonce(
+            &self.unstake_token().get_token_id(),
+            unbond_epoch,
+            unstake_epoch,
+            tier_id,
+        );
+        if epoch_nonce_mapper.is_empty() {
+            0
+        } else {
+            epoch_nonce_mapper.get().nonce
+        }
+    }
+
+    // Oldest first, empty when no per-epoch token was minted for the unbond epoch
+    #[view(getEpochUnstakeNonces)]
+    fn get_epoch_unstake_nonces(&self, unbond_epoch: u64) -> MultiValueEncoded<u64> {
+        let mut nonces = MultiValueEncoded::new();
+        for nonce in self
+            .epoch_unstake_nonces(&self.unstake_token().get_token_id(), unbond_epoch)
+            .iter()
+        {
+            nonces.push(nonce);
+        }
+
+        nonces
+    }
+
+    #[view(getMaxLossPerCall)]
+    fn get_max_loss_per_call(&self) -> u64 {
+        if self.max_loss_per_call().is_empty() {
+            DEFAULT_MAX_LOSS_PER_CALL
+        } else {
+            self.max_loss_per_call().get()
+        }
+    }
+
+    #[view(getUnstakeValueIndex)]
+    fn get_unstake_value_index(&self) -> BigUint {
+        if self.unstake_value_index().is_empty() {
+            BigUint::from(INITIAL_EXCHANGE_RATE)
+        } else {
+            self.unstake_value_index().get()
+        }
+    }
+
+    fn get_unbond_tier(&self, tier_id: u8) -> UnbondTier {
+        self.find_unbond_tier(tier_id)
+            .unwrap_or_else(|| sc_panic!(ERROR_UNKNOWN_UNBOND_TIER))
+    }
+
+    fn find_unbond_tier(&self, tier_id: u8) -> Option<UnbondTier> {
+        match self.unbond_tiers().get(&tier_id) {
+            Some(tier) => Some(tier),
+            // The default tier keeps the original unbond period without fees until configured otherwise
+            None if tier_id == DEFAULT_UNBOND_TIER => Some(UnbondTier {
+                unbond_epochs: DEFAULT_UNBOND_PERIOD,
+                fee: 0,
+            }),
+            None => None,
+        }
+    }
+
+    #[view(getUnbondTiers)]
+    fn get_unbond_tiers(&self) -> MultiValueEncoded<MultiValue3<u8, u64, u64>> {
+        let mut tiers = MultiValueEncoded::new();
+        for (tier_id, tier) in self.unbond_tiers().iter() {
+            tiers.push((tier_id, tier.unbond_epochs, tier.fee).into());
+        }
+
+        tiers
+    }
+
     #[inline]
     fn is_state_active(&self, state: State) -> bool {
         state == State::Active
     }
 
+    #[inline]
+    fn is_emergency_mode(&self) -> bool {
+        self.state().get() == State::Emergency
+    }
+
     #[view(getState)]
     #[storage_mapper("state")]
     fn state(&self) -> SingleValueMapper<State>;
@@ -102,4 +608,110 @@ pub trait ConfigModule:
     #[view(getUnstakeTokenSupply)]
     #[storage_mapper("unstakeTokenSupply")]
     fn unstake_token_supply(&self) -> SingleValueMapper<BigUint>;
+
+    // Original amount of the unstake tokens not burned yet, unlike the unstake token supply no loss ever changes it
+    #[view(getOutstandingUnstakeAmount)]
+    #[storage_mapper("outstandingUnstakeAmount")]
+    fn outstanding_unstake_amount(&self) -> SingleValueMapper<BigUint>;
+
+    // Penalty in basis points charged when withdrawing right after unstaking, it declines linearly to zero at unbond_epoch
+    #[view(getEarlyWithdrawMaxPenalty)]
+    #[storage_mapper("earlyWithdrawMaxPenalty")]
+    fn early_withdraw_max_penalty(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("unbondTiers")]
+    fn unbond_tiers(&self) -> MapMapper<u8, UnbondTier>;
+
+    #[view(getMaxUnbondPerEpoch)]
+    #[storage_mapper("maxUnbondPerEpoch")]
+    fn max_unbond_per_epoch(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getScheduledUnbond)]
+    #[storage_mapper("scheduledUnbond")]
+    fn scheduled_unbond(&self, epoch: u64) -> SingleValueMapper<BigUint>;
+
+    // Only followed while the epoch is full, it then points to a later epoch that had unbond capacity left
+    // Every epoch in between stays full, releasing capacity points the cursors before it back at it
+    #[storage_mapper("nextOpenUnbondEpoch")]
+    fn next_open_unbond_epoch(&self, generation: u64, epoch: u64) -> SingleValueMapper<u64>;
+
+    // Changed with the unbond limit, the cursors of the previous generations are ignored
+    #[storage_mapper("unbondCursorGeneration")]
+    fn unbond_cursor_generation(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMaxTotalReserve)]
+    #[storage_mapper("maxTotalReserve")]
+    fn max_total_reserve(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMaxDelegationPerAddress)]
+    #[storage_mapper("maxDelegationPerAddress")]
+    fn max_delegation_per_address(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getDelegatedAmount)]
+    #[storage_mapper("delegatedAmount")]
+    fn delegated_amount(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinDelegateAmount)]
+    #[storage_mapper("minDelegateAmount")]
+    fn min_delegate_amount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinUnDelegateAmount)]
+    #[storage_mapper("minUnDelegateAmount")]
+    fn min_undelegate_amount(&self) -> SingleValueMapper<BigUint>;
+
+    // Unstaked amounts below this threshold are paid out immediately instead of minting an unstake token
+    #[view(getInstantWithdrawThreshold)]
+    #[storage_mapper("instantWithdrawThreshold")]
+    fn instant_withdraw_threshold(&self) -> SingleValueMapper<BigUint>;
+
+    // Maximum loss in basis points of the affected amount that a single applyLoss call can report
+    #[storage_mapper("maxLossPerCall")]
+    fn max_loss_per_call(&self) -> SingleValueMapper<u64>;
+
+    // Value of one pending unstake unit, starts at INITIAL_EXCHANGE_RATE and only decreases through applyLoss
+    #[storage_mapper("unstakeValueIndex")]
+    fn unstake_value_index(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("unstakeTokenUris")]
+    fn unstake_token_uris(&self) -> SingleValueMapper<ManagedVec<ManagedBuffer>>;
+
+    #[storage_mapper("unstakeTokenNameTemplate")]
+    fn unstake_token_name_template(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getUnstakeTokenMode)]
+    #[storage_mapper("unstakeTokenMode")]
+    fn unstake_token_mode(&self) -> SingleValueMapper<UnstakeTokenMode>;
+
+    // Keyed by the unstake epoch and the tier as well as the unbond epoch, since the early withdraw penalty is computed from them
+    // and by the token identifier, so a replaced unstake token never reuses the nonces of the old one
+    #[storage_mapper("epochUnstakeNonce")]
+    fn epoch_unstake_nonce(
+        &self,
+        token_id: &TokenIdentifier,
+        unbond_epoch: u64,
+        unstake_epoch: u64,
+        tier_id: u8,
+    ) -> SingleValueMapper<EpochUnstakeNonce<Self::Api>>;
+
+    // Attributes every unstake token nonce was minted with, the contract no longer holds the token data once it sent the token
+    // Keyed by the token identifier, so a replaced unstake token never reads the positions of the old one
+    #[storage_mapper("unstakePositions")]
+    fn unstake_positions(
+        &self,
+        token_id: &TokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<UnstakeTokenAttributes<Self::Api>>;
+
+    // Every per-epoch nonce ever minted for an unbond epoch, whatever its unstake epoch, tier or value index
+    #[storage_mapper("epochUnstakeNonces")]
+    fn epoch_unstake_nonces(&self, token_id: &TokenIdentifier, epoch: u64) -> VecMapper<u64>;
+
+    #[view(getTreasury)]
+    #[storage_mapper("treasury")]
+    fn treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // XOXNO left over by delegations rounded down or by a reserve without LS tokens, owed to the treasury rather than the LS token holders
+    #[view(getRoundingDust)]
+    #[storage_mapper("roundingDust")]
+    fn rounding_dust(&self) -> SingleValueMapper<BigUint>;
 }
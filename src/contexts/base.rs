@@ -1,19 +1,55 @@
+use core::cell::OnceCell;
+
 use crate::liquidity_pool::State;
+use crate::pricing::{PoolState, PoolStateMut};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+// Every field is read from storage on first access, only the modified ones are written back on drop
 pub struct StorageCache<'a, C>
 where
     C: crate::config::ConfigModule,
 {
     sc_ref: &'a C,
-    pub contract_state: State,
-    pub main_token_id: TokenIdentifier<C::Api>,
-    pub ls_token_id: TokenIdentifier<C::Api>,
-    pub ls_token_supply: BigUint<C::Api>,
-    pub virtual_xoxno_reserve: BigUint<C::Api>,
-    pub total_unstaked_xoxno: BigUint<C::Api>,
+    contract_state: OnceCell<State>,
+    main_token_id: OnceCell<TokenIdentifier<C::Api>>,
+    ls_token_id: OnceCell<TokenIdentifier<C::Api>>,
+    ls_token_supply: CachedValue<BigUint<C::Api>>,
+    virtual_xoxno_reserve: CachedValue<BigUint<C::Api>>,
+    total_unstaked_xoxno: CachedValue<BigUint<C::Api>>,
+}
+
+struct CachedValue<T> {
+    value: OnceCell<T>,
+    dirty: bool,
+}
+
+impl<T> CachedValue<T> {
+    fn new() -> Self {
+        CachedValue {
+            value: OnceCell::new(),
+            dirty: false,
+        }
+    }
+
+    fn get(&self, load: impl FnOnce() -> T) -> &T {
+        self.value.get_or_init(load)
+    }
+
+    fn get_mut(&mut self, load: impl FnOnce() -> T) -> &mut T {
+        self.value.get_or_init(load);
+        self.dirty = true;
+        self.value.get_mut().unwrap()
+    }
+
+    fn modified(&self) -> Option<&T> {
+        if self.dirty {
+            self.value.get()
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a, C> StorageCache<'a, C>
@@ -22,15 +58,64 @@ where
 {
     pub fn new(sc_ref: &'a C) -> Self {
         StorageCache {
-            contract_state: sc_ref.state().get(),
-            main_token_id: sc_ref.main_token().get(),
-            ls_token_id: sc_ref.ls_token().get_token_id(),
-            ls_token_supply: sc_ref.ls_token_supply().get(),
-            virtual_xoxno_reserve: sc_ref.virtual_xoxno_reserve().get(),
-            total_unstaked_xoxno: sc_ref.unstake_token_supply().get(),
             sc_ref,
+            contract_state: OnceCell::new(),
+            main_token_id: OnceCell::new(),
+            ls_token_id: OnceCell::new(),
+            ls_token_supply: CachedValue::new(),
+            virtual_xoxno_reserve: CachedValue::new(),
+            total_unstaked_xoxno: CachedValue::new(),
         }
     }
+
+    pub fn contract_state(&self) -> State {
+        *self
+            .contract_state
+            .get_or_init(|| self.sc_ref.state().get())
+    }
+
+    pub fn main_token_id(&self) -> &TokenIdentifier<C::Api> {
+        self.main_token_id
+            .get_or_init(|| self.sc_ref.main_token().get())
+    }
+
+    pub fn ls_token_id(&self) -> &TokenIdentifier<C::Api> {
+        self.ls_token_id
+            .get_or_init(|| self.sc_ref.ls_token().get_token_id())
+    }
+
+    pub fn ls_token_supply(&self) -> &BigUint<C::Api> {
+        self.ls_token_supply
+            .get(|| self.sc_ref.ls_token_supply().get())
+    }
+
+    pub fn ls_token_supply_mut(&mut self) -> &mut BigUint<C::Api> {
+        let sc_ref = self.sc_ref;
+        self.ls_token_supply
+            .get_mut(|| sc_ref.ls_token_supply().get())
+    }
+
+    pub fn virtual_xoxno_reserve(&self) -> &BigUint<C::Api> {
+        self.virtual_xoxno_reserve
+            .get(|| self.sc_ref.virtual_xoxno_reserve().get())
+    }
+
+    pub fn virtual_xoxno_reserve_mut(&mut self) -> &mut BigUint<C::Api> {
+        let sc_ref = self.sc_ref;
+        self.virtual_xoxno_reserve
+            .get_mut(|| sc_ref.virtual_xoxno_reserve().get())
+    }
+
+    pub fn total_unstaked_xoxno(&self) -> &BigUint<C::Api> {
+        self.total_unstaked_xoxno
+            .get(|| self.sc_ref.unstake_token_supply().get())
+    }
+
+    pub fn total_unstaked_xoxno_mut(&mut self) -> &mut BigUint<C::Api> {
+        let sc_ref = self.sc_ref;
+        self.total_unstaked_xoxno
+            .get_mut(|| sc_ref.unstake_token_supply().get())
+    }
 }
 
 impl<'a, C> Drop for StorageCache<'a, C>
@@ -38,13 +123,43 @@ where
     C: crate::config::ConfigModule,
 {
     fn drop(&mut self) {
-        // commit changes to storage for the mutable fields
-        self.sc_ref.ls_token_supply().set(&self.ls_token_supply);
-        self.sc_ref
-            .virtual_xoxno_reserve()
-            .set(&self.virtual_xoxno_reserve);
-        self.sc_ref
-            .unstake_token_supply()
-            .set(&self.total_unstaked_xoxno);
+        // commit changes to storage for the mutable fields that were modified
+        if let Some(ls_token_supply) = self.ls_token_supply.modified() {
+            self.sc_ref.ls_token_supply().set(ls_token_supply);
+        }
+        if let Some(virtual_xoxno_reserve) = self.virtual_xoxno_reserve.modified() {
+            self.sc_ref
+                .virtual_xoxno_reserve()
+                .set(virtual_xoxno_reserve);
+        }
+        if let Some(total_unstaked_xoxno) = self.total_unstaked_xoxno.modified() {
+            self.sc_ref.unstake_token_supply().set(total_unstaked_xoxno);
+        }
+    }
+}
+
+impl<'a, C> PoolState<C::Api> for StorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    fn ls_token_supply(&self) -> &BigUint<C::Api> {
+        StorageCache::ls_token_supply(self)
+    }
+
+    fn virtual_xoxno_reserve(&self) -> &BigUint<C::Api> {
+        StorageCache::virtual_xoxno_reserve(self)
+    }
+}
+
+impl<'a, C> PoolStateMut<C::Api> for StorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    fn ls_token_supply_mut(&mut self) -> &mut BigUint<C::Api> {
+        StorageCache::ls_token_supply_mut(self)
+    }
+
+    fn virtual_xoxno_reserve_mut(&mut self) -> &mut BigUint<C::Api> {
+        StorageCache::virtual_xoxno_reserve_mut(self)
     }
 }
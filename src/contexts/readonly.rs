@@ -1,19 +1,23 @@
+use core::cell::OnceCell;
+
 use crate::liquidity_pool::State;
+use crate::pricing::PoolState;
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+// Same lazily loaded fields as StorageCache, without ever writing back
 pub struct ReadOnlyStorageCache<'a, C>
 where
     C: crate::config::ConfigModule,
 {
-    _sc_ref: &'a C,
-    pub contract_state: State,
-    pub main_token_id: TokenIdentifier<C::Api>,
-    pub ls_token_id: TokenIdentifier<C::Api>,
-    pub ls_token_supply: BigUint<C::Api>,
-    pub virtual_xoxno_reserve: BigUint<C::Api>,
-    pub total_unstaked_xoxno: BigUint<C::Api>,
+    sc_ref: &'a C,
+    contract_state: OnceCell<State>,
+    main_token_id: OnceCell<TokenIdentifier<C::Api>>,
+    ls_token_id: OnceCell<TokenIdentifier<C::Api>>,
+    ls_token_supply: OnceCell<BigUint<C::Api>>,
+    virtual_xoxno_reserve: OnceCell<BigUint<C::Api>>,
+    total_unstaked_xoxno: OnceCell<BigUint<C::Api>>,
 }
 
 impl<'a, C> ReadOnlyStorageCache<'a, C>
@@ -22,13 +26,57 @@ where
 {
     pub fn new(sc_ref: &'a C) -> Self {
         ReadOnlyStorageCache {
-            contract_state: sc_ref.state().get(),
-            main_token_id: sc_ref.main_token().get(),
-            ls_token_id: sc_ref.ls_token().get_token_id(),
-            ls_token_supply: sc_ref.ls_token_supply().get(),
-            virtual_xoxno_reserve: sc_ref.virtual_xoxno_reserve().get(),
-            total_unstaked_xoxno: sc_ref.unstake_token_supply().get(),
-            _sc_ref: sc_ref,
+            sc_ref,
+            contract_state: OnceCell::new(),
+            main_token_id: OnceCell::new(),
+            ls_token_id: OnceCell::new(),
+            ls_token_supply: OnceCell::new(),
+            virtual_xoxno_reserve: OnceCell::new(),
+            total_unstaked_xoxno: OnceCell::new(),
         }
     }
+
+    pub fn contract_state(&self) -> State {
+        *self
+            .contract_state
+            .get_or_init(|| self.sc_ref.state().get())
+    }
+
+    pub fn main_token_id(&self) -> &TokenIdentifier<C::Api> {
+        self.main_token_id
+            .get_or_init(|| self.sc_ref.main_token().get())
+    }
+
+    pub fn ls_token_id(&self) -> &TokenIdentifier<C::Api> {
+        self.ls_token_id
+            .get_or_init(|| self.sc_ref.ls_token().get_token_id())
+    }
+
+    pub fn ls_token_supply(&self) -> &BigUint<C::Api> {
+        self.ls_token_supply
+            .get_or_init(|| self.sc_ref.ls_token_supply().get())
+    }
+
+    pub fn virtual_xoxno_reserve(&self) -> &BigUint<C::Api> {
+        self.virtual_xoxno_reserve
+            .get_or_init(|| self.sc_ref.virtual_xoxno_reserve().get())
+    }
+
+    pub fn total_unstaked_xoxno(&self) -> &BigUint<C::Api> {
+        self.total_unstaked_xoxno
+            .get_or_init(|| self.sc_ref.unstake_token_supply().get())
+    }
+}
+
+impl<'a, C> PoolState<C::Api> for ReadOnlyStorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    fn ls_token_supply(&self) -> &BigUint<C::Api> {
+        ReadOnlyStorageCache::ls_token_supply(self)
+    }
+
+    fn virtual_xoxno_reserve(&self) -> &BigUint<C::Api> {
+        ReadOnlyStorageCache::virtual_xoxno_reserve(self)
+    }
 }
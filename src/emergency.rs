@@ -0,0 +1,110 @@
+multiversx_sc::imports!();
+
+use crate::contexts::base::StorageCache;
+use crate::errors::*;
+use crate::liquidity_pool::State;
+
+#[multiversx_sc::module]
+pub trait EmergencyModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::liquidity_pool::LiquidityPoolModule
+    + crate::stats::StatsModule
+    + crate::events::EventsModule
+    + crate::reports::ReportsModule
+    + crate::airdrop::AirdropModule
+    + crate::migration::MigrationModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // Irreversible, every other user endpoint stays disabled afterwards
+    #[endpoint(enableEmergencyMode)]
+    fn enable_emergency_mode(&self) {
+        self.require_owner();
+        require!(!self.is_emergency_mode(), ERROR_EMERGENCY_MODE);
+
+        self.state().set(State::Emergency);
+
+        // Airdrop funds were never delegated, they stay reserved until the owner takes them back through cancelAirdrop
+        let storage_cache = StorageCache::new(self);
+        let redeemable_balance =
+            self.get_emergency_redeemable_balance(storage_cache.main_token_id());
+        self.emit_emergency_mode_event(&storage_cache, &redeemable_balance);
+    }
+
+    // Redeems LS and unstake tokens pro-rata against the main token balance held by the contract
+    #[payable("*")]
+    #[endpoint(emergencyRedeem)]
+    fn emergency_redeem(&self) -> EsdtTokenPayment {
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+
+        require!(
+            storage_cache.contract_state() == State::Emergency,
+            ERROR_NOT_EMERGENCY_MODE
+        );
+        require!(!payments.is_empty(), ERROR_BAD_PAYMENT_AMOUNT);
+
+        let total_claims =
+            storage_cache.virtual_xoxno_reserve() + storage_cache.total_unstaked_xoxno();
+        require!(total_claims > 0, ERROR_NOTHING_TO_REDEEM);
+        let redeemable_balance =
+            self.get_emergency_redeemable_balance(storage_cache.main_token_id());
+        let unstake_token_id = self.unstake_token().get_token_id();
+
+        let mut claim_amount = BigUint::zero();
+        for payment in payments.iter() {
+            require!(payment.amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
+
+            if payment.token_identifier == *storage_cache.ls_token_id() {
+                let xoxno_amount = self.pool_remove_liquidity(&payment.amount, &mut storage_cache);
+                self.burn_ls_token(&payment.amount);
+                claim_amount += xoxno_amount;
+            } else if payment.token_identifier == unstake_token_id {
+                let unstake_token_attributes =
+                    self.get_unstake_position(payment.token_nonce, &payment.amount);
+                let unstake_amount = self.compute_unstake_value(&unstake_token_attributes);
+                self.pool_release_unstaked(&unstake_amount, &mut storage_cache);
+                self.burn_unstake_tokens(
+                    payment.token_nonce,
+                    &payment.amount,
+                    &unstake_token_attributes,
+                );
+                claim_amount += unstake_amount;
+            } else {
+                sc_panic!(ERROR_BAD_PAYMENT_TOKEN);
+            }
+        }
+
+        let user_payment = EsdtTokenPayment::new(
+            storage_cache.main_token_id().clone(),
+            0,
+            claim_amount * &redeemable_balance / &total_claims,
+        );
+        // Redemptions pay out right away, so they count as withdrawals for LS tokens as well
+        self.record_withdrawal(&user_payment.amount);
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.update_epoch_report(current_epoch, &storage_cache, |report| {
+            report.withdrawn += &user_payment.amount;
+        });
+        if user_payment.amount > 0 {
+            self.tx().to(&caller).payment(&user_payment).transfer();
+        }
+
+        user_payment
+    }
+
+    fn get_emergency_redeemable_balance(&self, main_token_id: &TokenIdentifier) -> BigUint {
+        let balance = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::esdt(main_token_id.clone()), 0);
+        // Neither the pending airdrops nor the rounding dust belong to the LS or unstake token holders
+        let reserved_amount = self.airdrop_pending_amount().get() + self.rounding_dust().get();
+        if balance > reserved_amount {
+            balance - reserved_amount
+        } else {
+            BigUint::zero()
+        }
+    }
+}
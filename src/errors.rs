@@ -1,12 +1,46 @@
 pub static ERROR_NOT_ACTIVE: &[u8] = b"Not active";
+pub static ERROR_EMERGENCY_MODE: &[u8] = b"Emergency mode is enabled";
+pub static ERROR_NOT_EMERGENCY_MODE: &[u8] = b"Emergency mode is not enabled";
+pub static ERROR_NOTHING_TO_REDEEM: &[u8] = b"Nothing to redeem";
 pub static ERROR_LS_TOKEN_NOT_ISSUED: &[u8] = b"LS token not issued";
+pub static ERROR_INVALID_TOKEN_ID: &[u8] = b"Invalid token identifier";
+pub static ERROR_UNSTAKE_TOKENS_OUTSTANDING: &[u8] = b"Unstake tokens are still outstanding";
+pub static ERROR_UNSTAKE_TOKEN_TYPE_MISMATCH: &[u8] = b"Unstake token type does not match the mode";
 
 pub static ERROR_UNSTAKE_PERIOD_NOT_PASSED: &[u8] = b"The unstake period has not passed";
+pub static ERROR_UNKNOWN_UNBOND_TIER: &[u8] = b"Unknown unbond tier";
+pub static ERROR_UNKNOWN_UNSTAKE_POSITION: &[u8] = b"Unknown unstake token nonce";
+pub static ERROR_UNBOND_LIMIT_EXCEEDED: &[u8] = b"Amount exceeds the unbond limit per epoch";
+pub static ERROR_EARLY_WITHDRAW_DISABLED: &[u8] = b"Early withdraw is not enabled";
+pub static ERROR_INVALID_NAME_TEMPLATE: &[u8] = b"Invalid unstake token name template";
 
 pub static ERROR_WRONG_TOKEN: &[u8] = b"Bad staking token";
 pub static ERROR_BAD_PAYMENT_TOKEN: &[u8] = b"Bad payment token";
 pub static ERROR_BAD_PAYMENT_AMOUNT: &[u8] = b"Insufficient delegated amount";
+pub static ERROR_DELEGATE_AMOUNT_TOO_LOW: &[u8] = b"Delegate amount below minimum";
+pub static ERROR_UNDELEGATE_AMOUNT_TOO_LOW: &[u8] = b"Undelegate amount below minimum";
 pub static ERROR_INSUFFICIENT_LIQUIDITY: &[u8] = b"Insufficient liquidity minted";
 pub static ERROR_INSUFFICIENT_LIQ_BURNED: &[u8] = b"Insufficient liquidity burned";
+pub static ERROR_TOTAL_CAP_EXCEEDED: &[u8] = b"Total delegation cap exceeded";
+pub static ERROR_ADDRESS_CAP_EXCEEDED: &[u8] = b"Address delegation cap exceeded";
+pub static ERROR_NO_RECIPIENTS: &[u8] = b"No recipients provided";
+pub static ERROR_ZERO_WEIGHT: &[u8] = b"Recipient weight must be positive";
+
+pub static ERROR_AIRDROP_AMOUNT_MISMATCH: &[u8] = b"Payment does not match the airdrop entries";
+pub static ERROR_NO_AIRDROP_ENTRIES: &[u8] = b"No airdrop entries to process";
 
 pub static ERROR_NOT_ENOUGH_LP: &[u8] = b"Not enough LP token supply";
+pub static ERROR_INVALID_PRECISION: &[u8] = b"Exchange rate precision too high";
+
+pub static ERROR_INVALID_PERCENTAGE: &[u8] = b"Invalid percentage";
+pub static ERROR_LOSS_TOO_HIGH: &[u8] = b"Loss exceeds the maximum allowed per call";
+pub static ERROR_NOTHING_TO_SLASH: &[u8] = b"Nothing to apply the loss to";
+pub static ERROR_TREASURY_NOT_SET: &[u8] = b"Treasury address not set";
+pub static ERROR_NO_ROUNDING_DUST: &[u8] = b"No rounding dust to sweep";
+
+pub static ERROR_UNKNOWN_STORAGE_VERSION: &[u8] = b"Unknown storage version";
+
+pub static ERROR_ONLY_OWNER: &[u8] = b"Endpoint can only be called by owner";
+pub static ERROR_INVALID_ADDRESS: &[u8] = b"Invalid address";
+pub static ERROR_NOT_PENDING_OWNER: &[u8] = b"Caller is not the pending owner";
+pub static ERROR_NO_PENDING_OWNER: &[u8] = b"No pending owner";
@@ -32,6 +32,21 @@ pub struct RemoveLiquidityEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[type_abi]
+#[derive(TopEncode)]
+pub struct InstantWithdrawEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    ls_token_id: TokenIdentifier<M>, // LXOXNO token
+    ls_token_amount: BigUint<M>, // LXOXNO unstaked
+    ls_token_supply: BigUint<M>, // LXOXNO total supply after unstake
+    main_token_id: TokenIdentifier<M>, // XOXNO token
+    withdrawn_amount: BigUint<M>, // XOXNO paid out right away, no unstake token is minted
+    virtual_xoxno_reserve: BigUint<M>, // Current XOXNO reserves (staked + rewards) after unstake
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[type_abi]
 #[derive(TopEncode)]
 pub struct AddRewardsEvent<M: ManagedTypeApi> {
@@ -45,9 +60,97 @@ pub struct AddRewardsEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[type_abi]
+#[derive(TopEncode)]
+pub struct EarlyWithdrawEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    main_token_id: TokenIdentifier<M>, // XOXNO token
+    withdrawn_amount: BigUint<M>, // XOXNO received after the penalty was applied
+    penalty_amount: BigUint<M>, // XOXNO kept by the pool as penalty
+    ls_token_supply: BigUint<M>, // LXOXNO supply at current early withdraw event
+    virtual_xoxno_reserve: BigUint<M>, // New XOXNO total reserve including the penalty
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct ApplyLossEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    ls_token_id: TokenIdentifier<M>, // LXOXNO token
+    ls_token_supply: BigUint<M>, // LXOXNO supply at current loss event
+    virtual_xoxno_reserve: BigUint<M>, // New XOXNO total reserve after the loss
+    reserve_loss: BigUint<M>, // XOXNO removed from the reserve
+    pending_loss: BigUint<M>, // XOXNO removed from the pending unstakes
+    exchange_rate: BigUint<M>, // New exchange rate, scaled by INITIAL_EXCHANGE_RATE
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct EmergencyModeEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    ls_token_id: TokenIdentifier<M>, // LXOXNO token
+    ls_token_supply: BigUint<M>, // LXOXNO supply when the emergency mode was enabled
+    virtual_xoxno_reserve: BigUint<M>, // XOXNO claimable by the LXOXNO holders
+    total_unstaked_xoxno: BigUint<M>, // XOXNO claimable by the unstake token holders
+    redeemable_balance: BigUint<M>, // XOXNO balance distributed pro-rata between all the claims
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SweepRoundingDustEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    treasury: ManagedAddress<M>, // Address that received the dust
+    main_token_id: TokenIdentifier<M>, // XOXNO token
+    dust_amount: BigUint<M>, // XOXNO sent to the treasury
+    ls_token_supply: BigUint<M>, // LXOXNO supply at current sweep event
+    virtual_xoxno_reserve: BigUint<M>, // Current XOXNO total reserve, the dust was never part of it
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipProposedEvent<M: ManagedTypeApi> {
+    owner: ManagedAddress<M>, // Admin that proposed the transfer
+    pending_owner: ManagedAddress<M>, // Address that has to accept the ownership
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipTransferredEvent<M: ManagedTypeApi> {
+    previous_owner: ManagedAddress<M>,
+    new_owner: ManagedAddress<M>, // The pending owner that accepted
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipProposalCancelledEvent<M: ManagedTypeApi> {
+    owner: ManagedAddress<M>, // Admin that keeps the ownership
+    pending_owner: ManagedAddress<M>, // Address that can no longer accept
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule:
     crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
 {
     fn emit_delegate_event(
@@ -59,16 +162,16 @@ pub trait EventsModule:
     ) {
         let epoch = self.blockchain().get_block_epoch();
         self.add_liquidity_event(
-            &storage_cache.ls_token_id,
+            storage_cache.ls_token_id(),
             caller,
             epoch,
             &AddLiquidityEvent {
                 caller: caller.clone(),
-                ls_token_id: storage_cache.ls_token_id.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
                 ls_token_amount: ls_token_amount.clone(),
-                ls_token_supply: storage_cache.ls_token_supply.clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
                 original_amount: original_amount.clone(),
-                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve.clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
                 block: self.blockchain().get_block_nonce(),
                 epoch,
                 timestamp: self.blockchain().get_block_timestamp(),
@@ -86,17 +189,44 @@ pub trait EventsModule:
         let epoch = self.blockchain().get_block_epoch();
         let caller = self.blockchain().get_caller();
         self.remove_liquidity_event(
-            &storage_cache.ls_token_id,
+            storage_cache.ls_token_id(),
             &caller,
             epoch,
             &RemoveLiquidityEvent {
                 caller: caller.clone(),
-                ls_token_id: storage_cache.ls_token_id.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
                 ls_token_amount: ls_token_amount.clone(),
                 unbound_nft,
                 original_amount: unstake_token_amount,
-                ls_token_supply: storage_cache.ls_token_supply.clone(),
-                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve.clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_instant_withdraw_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        caller: &ManagedAddress,
+        ls_token_amount: &BigUint,
+        withdrawn_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.instant_withdraw_event(
+            storage_cache.ls_token_id(),
+            caller,
+            epoch,
+            &InstantWithdrawEvent {
+                caller: caller.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
+                ls_token_amount: ls_token_amount.clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                main_token_id: storage_cache.main_token_id().clone(),
+                withdrawn_amount: withdrawn_amount.clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
                 block: self.blockchain().get_block_nonce(),
                 epoch,
                 timestamp: self.blockchain().get_block_timestamp(),
@@ -112,14 +242,14 @@ pub trait EventsModule:
     ) {
         let epoch = self.blockchain().get_block_epoch();
         self.add_rewards_event(
-            &storage_cache.ls_token_id,
+            storage_cache.ls_token_id(),
             caller,
             epoch,
             &AddRewardsEvent {
                 caller: caller.clone(),
-                ls_token_id: storage_cache.ls_token_id.clone(),
-                ls_token_supply: storage_cache.ls_token_supply.clone(),
-                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
                 rewards_amount: reward_amount.clone(),
                 block: self.blockchain().get_block_nonce(),
                 epoch,
@@ -128,6 +258,171 @@ pub trait EventsModule:
         )
     }
 
+    fn emit_early_withdraw_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        caller: &ManagedAddress,
+        withdrawn_amount: &BigUint,
+        penalty_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.early_withdraw_event(
+            storage_cache.main_token_id(),
+            caller,
+            epoch,
+            &EarlyWithdrawEvent {
+                caller: caller.clone(),
+                main_token_id: storage_cache.main_token_id().clone(),
+                withdrawn_amount: withdrawn_amount.clone(),
+                penalty_amount: penalty_amount.clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_apply_loss_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        reserve_loss: &BigUint,
+        pending_loss: &BigUint,
+        exchange_rate: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.apply_loss_event(
+            storage_cache.ls_token_id(),
+            &caller,
+            epoch,
+            &ApplyLossEvent {
+                caller: caller.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+                reserve_loss: reserve_loss.clone(),
+                pending_loss: pending_loss.clone(),
+                exchange_rate,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_emergency_mode_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        redeemable_balance: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.emergency_mode_event(
+            storage_cache.ls_token_id(),
+            &caller,
+            epoch,
+            &EmergencyModeEvent {
+                caller: caller.clone(),
+                ls_token_id: storage_cache.ls_token_id().clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+                total_unstaked_xoxno: storage_cache.total_unstaked_xoxno().clone(),
+                redeemable_balance: redeemable_balance.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_sweep_rounding_dust_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        treasury: &ManagedAddress,
+        dust_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.sweep_rounding_dust_event(
+            storage_cache.main_token_id(),
+            &caller,
+            epoch,
+            &SweepRoundingDustEvent {
+                caller: caller.clone(),
+                treasury: treasury.clone(),
+                main_token_id: storage_cache.main_token_id().clone(),
+                dust_amount: dust_amount.clone(),
+                ls_token_supply: storage_cache.ls_token_supply().clone(),
+                virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_ownership_proposed_event(
+        &self,
+        owner: &ManagedAddress,
+        pending_owner: &ManagedAddress,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.ownership_proposed_event(
+            owner,
+            pending_owner,
+            epoch,
+            &OwnershipProposedEvent {
+                owner: owner.clone(),
+                pending_owner: pending_owner.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_ownership_transferred_event(
+        &self,
+        previous_owner: &ManagedAddress,
+        new_owner: &ManagedAddress,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.ownership_transferred_event(
+            previous_owner,
+            new_owner,
+            epoch,
+            &OwnershipTransferredEvent {
+                previous_owner: previous_owner.clone(),
+                new_owner: new_owner.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_ownership_proposal_cancelled_event(
+        &self,
+        owner: &ManagedAddress,
+        pending_owner: &ManagedAddress,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.ownership_proposal_cancelled_event(
+            owner,
+            pending_owner,
+            epoch,
+            &OwnershipProposalCancelledEvent {
+                owner: owner.clone(),
+                pending_owner: pending_owner.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("add_liquidity")]
     fn add_liquidity_event(
         &self,
@@ -146,6 +441,15 @@ pub trait EventsModule:
         #[indexed] remove_liquidity_event: &RemoveLiquidityEvent<Self::Api>,
     );
 
+    #[event("instant_withdraw")]
+    fn instant_withdraw_event(
+        &self,
+        #[indexed] ls_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] instant_withdraw_event: &InstantWithdrawEvent<Self::Api>,
+    );
+
     #[event("add_rewards")]
     fn add_rewards_event(
         &self,
@@ -154,4 +458,67 @@ pub trait EventsModule:
         #[indexed] epoch: u64,
         #[indexed] add_rewards_event: &AddRewardsEvent<Self::Api>,
     );
+
+    #[event("early_withdraw")]
+    fn early_withdraw_event(
+        &self,
+        #[indexed] main_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] early_withdraw_event: &EarlyWithdrawEvent<Self::Api>,
+    );
+
+    #[event("apply_loss")]
+    fn apply_loss_event(
+        &self,
+        #[indexed] ls_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] apply_loss_event: &ApplyLossEvent<Self::Api>,
+    );
+
+    #[event("emergency_mode")]
+    fn emergency_mode_event(
+        &self,
+        #[indexed] ls_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] emergency_mode_event: &EmergencyModeEvent<Self::Api>,
+    );
+
+    #[event("sweep_rounding_dust")]
+    fn sweep_rounding_dust_event(
+        &self,
+        #[indexed] main_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] sweep_rounding_dust_event: &SweepRoundingDustEvent<Self::Api>,
+    );
+
+    #[event("ownership_proposed")]
+    fn ownership_proposed_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] pending_owner: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] ownership_proposed_event: &OwnershipProposedEvent<Self::Api>,
+    );
+
+    #[event("ownership_transferred")]
+    fn ownership_transferred_event(
+        &self,
+        #[indexed] previous_owner: &ManagedAddress,
+        #[indexed] new_owner: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] ownership_transferred_event: &OwnershipTransferredEvent<Self::Api>,
+    );
+
+    #[event("ownership_proposal_cancelled")]
+    fn ownership_proposal_cancelled_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] pending_owner: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] ownership_proposal_cancelled_event: &OwnershipProposalCancelledEvent<Self::Api>,
+    );
 }
@@ -2,24 +2,51 @@
 
 #[allow(unused_imports)]
 use multiversx_sc::imports::*;
+pub mod airdrop;
 pub mod config;
 pub mod contexts;
+pub mod emergency;
 pub mod errors;
 pub mod events;
 pub mod liquidity_pool;
+pub mod migration;
+#[cfg(feature = "offchain")]
+pub mod offchain;
+pub mod ownership;
+pub mod ownership_transfer;
+pub mod pricing;
+pub mod quotes;
+pub mod reports;
 pub mod rs_xoxno_proxy;
+pub mod stats;
+pub mod tokens;
 
 use crate::{
-    config::{UnstakeTokenAttributes, INITIAL_EXCHANGE_RATE, UNBOND_PERIOD},
+    config::{
+        ContractSummary, UnstakePositionInfo, UnstakeTokenAttributes, DEFAULT_UNBOND_TIER,
+        MAX_EXCHANGE_RATE_DECIMALS, MAX_PERCENTAGE,
+    },
     errors::*,
 };
 use contexts::{base::*, readonly::ReadOnlyStorageCache};
+use liquidity_pool::State;
+use migration::CURRENT_STORAGE_VERSION;
 
 #[multiversx_sc::contract]
+#[esdt_attribute("UXOXNO", config::UnstakeTokenAttributes<Self::Api>)]
 pub trait RsLiquidXoxno:
     config::ConfigModule
     + liquidity_pool::LiquidityPoolModule
+    + stats::StatsModule
     + events::EventsModule
+    + reports::ReportsModule
+    + migration::MigrationModule
+    + airdrop::AirdropModule
+    + emergency::EmergencyModule
+    + ownership::OwnershipModule
+    + ownership_transfer::OwnershipTransferModule
+    + tokens::TokensModule
+    + quotes::QuotesModule
     + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + ContractBase
@@ -27,10 +54,15 @@ pub trait RsLiquidXoxno:
     #[init]
     fn init(&self, main_token: &TokenIdentifier) {
         self.main_token().set(main_token);
+        self.contract_owner().set(self.blockchain().get_caller());
+        self.storage_version().set(CURRENT_STORAGE_VERSION);
     }
 
+    // Long migrations are checkpointed and resumed through migrateStorage
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        self.run_storage_migrations();
+    }
 
     #[payable("*")]
     #[endpoint(delegate)]
@@ -41,66 +73,168 @@ pub trait RsLiquidXoxno:
             OptionalValue::None => self.blockchain().get_caller(),
         };
 
-        let staked_tokens = self.call_value().single_esdt();
+        let staked_amount = self.get_main_token_payments_amount(storage_cache.main_token_id());
+
         require!(
-            staked_tokens.token_identifier == storage_cache.main_token_id,
-            ERROR_WRONG_TOKEN
+            self.is_state_active(storage_cache.contract_state()),
+            ERROR_NOT_ACTIVE
         );
 
         require!(
-            self.is_state_active(storage_cache.contract_state),
-            ERROR_NOT_ACTIVE
+            staked_amount >= self.min_delegate_amount().get(),
+            ERROR_DELEGATE_AMOUNT_TOO_LOW
         );
+        self.track_beneficiary_delegation(&user, &staked_amount);
 
-        let ls_token_amount = self.pool_add_liquidity(&staked_tokens.amount, &mut storage_cache);
-        let user_payment = self.mint_ls_token(ls_token_amount);
+        let user_payment = self.process_delegation(&staked_amount, &mut storage_cache);
         self.tx().to(&user).payment(&user_payment).transfer();
 
-        self.emit_delegate_event(
-            &storage_cache,
-            &user,
-            &user_payment.amount,
-            &staked_tokens.amount,
-        );
+        self.emit_delegate_event(&storage_cache, &user, &user_payment.amount, &staked_amount);
 
         user_payment
     }
 
+    #[payable("*")]
+    #[endpoint(delegateSplit)]
+    fn delegate_split(
+        &self,
+        recipients: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let mut storage_cache = StorageCache::new(self);
+        let staked_amount = self.get_main_token_payments_amount(storage_cache.main_token_id());
+
+        require!(
+            self.is_state_active(storage_cache.contract_state()),
+            ERROR_NOT_ACTIVE
+        );
+        require!(!recipients.is_empty(), ERROR_NO_RECIPIENTS);
+
+        let mut total_weight = 0u64;
+        let mut shares = ManagedVec::<Self::Api, MultiValue2<ManagedAddress, u64>>::new();
+        for recipient in recipients.into_iter() {
+            let (_, weight) = recipient.clone().into_tuple();
+            require!(weight > 0, ERROR_ZERO_WEIGHT);
+
+            total_weight += weight;
+            shares.push(recipient);
+        }
+
+        require!(
+            staked_amount >= self.min_delegate_amount().get(),
+            ERROR_DELEGATE_AMOUNT_TOO_LOW
+        );
+
+        let ls_payment = self.process_delegation(&staked_amount, &mut storage_cache);
+        let ls_token_amount = ls_payment.amount;
+        let total_weight = BigUint::from(total_weight);
+        let last_index = shares.len() - 1;
+
+        // The last recipient receives the rounding remainder so the whole minted amount is distributed
+        let mut ls_left = ls_token_amount.clone();
+        let mut staked_left = staked_amount.clone();
+        let mut user_payments = MultiValueEncoded::new();
+        for (index, share) in shares.into_iter().enumerate() {
+            let (recipient, weight) = share.into_tuple();
+            let (ls_share, staked_share) = if index == last_index {
+                (ls_left.clone(), staked_left.clone())
+            } else {
+                (
+                    &ls_token_amount * weight / &total_weight,
+                    &staked_amount * weight / &total_weight,
+                )
+            };
+            ls_left -= &ls_share;
+            staked_left -= &staked_share;
+
+            require!(ls_share > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+            self.track_beneficiary_delegation(&recipient, &staked_share);
+
+            let user_payment =
+                EsdtTokenPayment::new(ls_payment.token_identifier.clone(), 0, ls_share);
+            self.tx().to(&recipient).payment(&user_payment).transfer();
+            self.emit_delegate_event(
+                &storage_cache,
+                &recipient,
+                &user_payment.amount,
+                &staked_share,
+            );
+
+            user_payments.push(user_payment);
+        }
+
+        user_payments
+    }
+
     #[payable("*")]
     #[endpoint(unDelegate)]
-    fn un_delegate(&self) {
+    fn un_delegate(&self, tier_id: OptionalValue<u8>) {
         let mut storage_cache = StorageCache::new(self);
         let caller = self.blockchain().get_caller();
         let payment = self.call_value().single_esdt();
 
         require!(
-            self.is_state_active(storage_cache.contract_state),
+            self.is_state_active(storage_cache.contract_state()),
             ERROR_NOT_ACTIVE
         );
         require!(
-            storage_cache.ls_token_id.is_valid_esdt_identifier(),
+            storage_cache.ls_token_id().is_valid_esdt_identifier(),
             ERROR_LS_TOKEN_NOT_ISSUED
         );
         require!(
-            payment.token_identifier == storage_cache.ls_token_id,
+            payment.token_identifier == *storage_cache.ls_token_id(),
             ERROR_BAD_PAYMENT_TOKEN
         );
         require!(payment.amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
 
-        let xoxno_to_unstake = self.pool_remove_liquidity(&payment.amount, &mut storage_cache);
+        let tier_id = tier_id.into_option().unwrap_or(DEFAULT_UNBOND_TIER);
+        let tier = self.get_unbond_tier(tier_id);
+
+        let undelegation = self.pool_undelegate(&payment.amount, tier.fee, &mut storage_cache);
+        require!(
+            undelegation.xoxno_removed >= self.min_undelegate_amount().get(),
+            ERROR_UNDELEGATE_AMOUNT_TOO_LOW
+        );
 
         self.burn_ls_token(&payment.amount);
+        let xoxno_to_unstake = undelegation.xoxno_to_unstake;
+        self.record_undelegation(&xoxno_to_unstake);
 
         let current_epoch = self.blockchain().get_block_epoch();
-        let unbond_epoch = current_epoch + UNBOND_PERIOD;
+        if self.is_instant_withdraw(&xoxno_to_unstake, current_epoch) {
+            self.schedule_unbond(&xoxno_to_unstake, current_epoch);
+            self.record_withdrawal(&xoxno_to_unstake);
+            self.update_epoch_report(current_epoch, &storage_cache, |report| {
+                report.undelegated += &xoxno_to_unstake;
+                report.withdrawn += &xoxno_to_unstake;
+            });
+            self.tx()
+                .to(&caller)
+                .single_esdt(storage_cache.main_token_id(), 0, &xoxno_to_unstake)
+                .transfer();
+            self.emit_instant_withdraw_event(
+                &storage_cache,
+                &caller,
+                &payment.amount,
+                &xoxno_to_unstake,
+            );
+            return;
+        }
 
-        storage_cache.total_unstaked_xoxno += &xoxno_to_unstake;
+        let unbond_epoch =
+            self.schedule_unbond(&xoxno_to_unstake, current_epoch + tier.unbond_epochs);
+
+        *storage_cache.total_unstaked_xoxno_mut() += &xoxno_to_unstake;
+        self.update_epoch_report(current_epoch, &storage_cache, |report| {
+            report.undelegated += &xoxno_to_unstake;
+        });
 
         let virtual_position = UnstakeTokenAttributes {
             unstake_epoch: current_epoch,
             original_amount: xoxno_to_unstake.clone(),
             share_amount: payment.amount.clone(),
             unbond_epoch,
+            tier_id,
+            unstake_value_index: self.get_unstake_value_index(),
         };
         let user_payment = self.mint_unstake_tokens(&virtual_position);
         self.tx().to(&caller).payment(&user_payment).transfer();
@@ -121,7 +255,7 @@ pub trait RsLiquidXoxno:
         self.unstake_token().require_all_same_token(&payments);
 
         require!(
-            self.is_state_active(storage_cache.contract_state),
+            self.is_state_active(storage_cache.contract_state()),
             ERROR_NOT_ACTIVE
         );
         let current_epoch = self.blockchain().get_block_epoch();
@@ -129,35 +263,97 @@ pub trait RsLiquidXoxno:
         for payment in payments.iter() {
             require!(payment.amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
 
-            let unstake_token_attributes: UnstakeTokenAttributes<Self::Api> = self
-                .unstake_token()
-                .get_token_attributes(payment.token_nonce);
+            let unstake_token_attributes =
+                self.get_unstake_position(payment.token_nonce, &payment.amount);
 
             require!(
                 current_epoch >= unstake_token_attributes.unbond_epoch,
                 ERROR_UNSTAKE_PERIOD_NOT_PASSED
             );
 
-            let unstake_amount = unstake_token_attributes.original_amount;
-
-            // Hnadle the case when the user tries to withdraw more than the total withdrawn amount (in case of the last user withdrawal)
-            if unstake_amount > storage_cache.total_unstaked_xoxno {
-                storage_cache.total_unstaked_xoxno = BigUint::from(0u64);
-            } else {
-                storage_cache.total_unstaked_xoxno -= &unstake_amount;
-            }
+            let unstake_amount = self.compute_unstake_value(&unstake_token_attributes);
+            self.pool_release_unstaked(&unstake_amount, &mut storage_cache);
 
             total_unstaked += unstake_amount;
-            self.burn_unstake_tokens(payment.token_nonce);
+            self.burn_unstake_tokens(
+                payment.token_nonce,
+                &payment.amount,
+                &unstake_token_attributes,
+            );
         }
+        self.record_withdrawal(&total_unstaked);
+        self.update_epoch_report(current_epoch, &storage_cache, |report| {
+            report.withdrawn += &total_unstaked;
+        });
         if total_unstaked > 0 {
             self.tx()
                 .to(&caller)
-                .single_esdt(&storage_cache.main_token_id, 0, &total_unstaked)
+                .single_esdt(storage_cache.main_token_id(), 0, &total_unstaked)
                 .transfer();
         }
     }
 
+    #[payable("*")]
+    #[endpoint(earlyWithdraw)]
+    fn early_withdraw(&self) -> EsdtTokenPayment {
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+
+        require!(
+            self.is_state_active(storage_cache.contract_state()),
+            ERROR_NOT_ACTIVE
+        );
+        require!(
+            payment.token_identifier == self.unstake_token().get_token_id(),
+            ERROR_BAD_PAYMENT_TOKEN
+        );
+        require!(
+            !self.early_withdraw_max_penalty().is_empty(),
+            ERROR_EARLY_WITHDRAW_DISABLED
+        );
+
+        require!(payment.amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
+        let unstake_token_attributes =
+            self.get_unstake_position(payment.token_nonce, &payment.amount);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let penalty = self.compute_early_withdraw_penalty(&unstake_token_attributes, current_epoch);
+        let unstake_amount = self.compute_unstake_value(&unstake_token_attributes);
+        self.pool_release_unstaked(&unstake_amount, &mut storage_cache);
+        if current_epoch < unstake_token_attributes.unbond_epoch {
+            self.release_scheduled_unbond(
+                &unstake_token_attributes.original_amount,
+                unstake_token_attributes.unbond_epoch,
+            );
+        }
+
+        // The penalty stays in the pool and is distributed to the remaining LS token holders
+        *storage_cache.virtual_xoxno_reserve_mut() += &penalty;
+        self.burn_unstake_tokens(
+            payment.token_nonce,
+            &payment.amount,
+            &unstake_token_attributes,
+        );
+
+        let user_payment = EsdtTokenPayment::new(
+            storage_cache.main_token_id().clone(),
+            0,
+            unstake_amount - &penalty,
+        );
+        self.record_withdrawal(&user_payment.amount);
+        self.update_epoch_report(current_epoch, &storage_cache, |report| {
+            report.withdrawn += &user_payment.amount;
+        });
+        if user_payment.amount > 0 {
+            self.tx().to(&caller).payment(&user_payment).transfer();
+        }
+
+        self.emit_early_withdraw_event(&storage_cache, &caller, &user_payment.amount, &penalty);
+
+        user_payment
+    }
+
     #[payable("*")]
     #[endpoint(addRewards)]
     fn add_rewards(&self) {
@@ -166,41 +362,253 @@ pub trait RsLiquidXoxno:
 
         let staked_tokens = self.call_value().single_esdt();
         require!(
-            staked_tokens.token_identifier == storage_cache.main_token_id,
+            staked_tokens.token_identifier == *storage_cache.main_token_id(),
             ERROR_WRONG_TOKEN
         );
 
         require!(
-            self.is_state_active(storage_cache.contract_state),
+            self.is_state_active(storage_cache.contract_state()),
             ERROR_NOT_ACTIVE
         );
-        storage_cache.virtual_xoxno_reserve += &staked_tokens.amount;
+        *storage_cache.virtual_xoxno_reserve_mut() += &staked_tokens.amount;
+        self.record_rewards(&staked_tokens.amount);
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.update_epoch_report(current_epoch, &storage_cache, |report| {
+            report.rewards += &staked_tokens.amount;
+        });
 
         self.emit_add_rewards_event(&storage_cache, &caller, &staked_tokens.amount);
     }
 
+    fn get_main_token_payments_amount(&self, main_token_id: &TokenIdentifier) -> BigUint {
+        let payments = self.call_value().all_esdt_transfers();
+        require!(!payments.is_empty(), ERROR_BAD_PAYMENT_AMOUNT);
+
+        let mut total_amount = BigUint::zero();
+        for payment in payments.iter() {
+            require!(
+                &payment.token_identifier == main_token_id,
+                ERROR_WRONG_TOKEN
+            );
+            total_amount += &payment.amount;
+        }
+
+        total_amount
+    }
+
+    #[endpoint(applyLoss)]
+    fn apply_loss(&self, loss_amount: BigUint, include_pending: bool) {
+        self.require_owner();
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            storage_cache.contract_state() != State::Emergency,
+            ERROR_EMERGENCY_MODE
+        );
+        require!(loss_amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
+
+        let (reserve_loss, pending_loss) =
+            self.pool_apply_loss(&loss_amount, include_pending, &mut storage_cache);
+        let exchange_rate = self.compute_exchange_rate(&storage_cache);
+        // Losses only move the end exchange rate of the report
+        self.update_epoch_report(self.blockchain().get_block_epoch(), &storage_cache, |_| {});
+
+        self.emit_apply_loss_event(&storage_cache, &reserve_loss, &pending_loss, exchange_rate);
+    }
+
+    // The dust is held next to the reserve, sweeping it leaves the exchange rate untouched
+    #[endpoint(sweepRoundingDust)]
+    fn sweep_rounding_dust(&self) -> BigUint {
+        self.require_owner();
+        let storage_cache = StorageCache::new(self);
+        require!(!self.treasury().is_empty(), ERROR_TREASURY_NOT_SET);
+
+        let dust_amount = self.rounding_dust().take();
+        require!(dust_amount > 0, ERROR_NO_ROUNDING_DUST);
+
+        let treasury = self.treasury().get();
+        self.tx()
+            .to(&treasury)
+            .single_esdt(storage_cache.main_token_id(), 0, &dust_amount)
+            .transfer();
+
+        self.emit_sweep_rounding_dust_event(&storage_cache, &treasury, &dust_amount);
+
+        dust_amount
+    }
+
     #[view(getMainTokenAmountForPosition)]
     fn get_ls_value_for_position(&self, ls_token_amount: BigUint) -> BigUint {
         let storage_cache = ReadOnlyStorageCache::new(self);
-        self.get_xoxno_amount_read_only(&ls_token_amount, &storage_cache)
+        self.get_xoxno_amount(&ls_token_amount, &storage_cache)
     }
 
     #[view(getLsTokenAmountForMainTokenAmount)]
     fn get_ls_amount_for_position(&self, main_token_amount: BigUint) -> BigUint {
         let storage_cache = ReadOnlyStorageCache::new(self);
-        self.get_ls_token_amount_readonly(&main_token_amount, &storage_cache)
+        self.get_ls_token_amount(&main_token_amount, &storage_cache)
+    }
+
+    // The amount is the quantity of a per-epoch unstake token and is ignored for per-position ones
+    #[view(getEarlyWithdrawPenalty)]
+    fn get_early_withdraw_penalty(&self, nonce: u64, amount: OptionalValue<BigUint>) -> BigUint {
+        let amount = amount.into_option().unwrap_or_else(|| BigUint::from(1u64));
+        let unstake_token_attributes = self
+            .try_get_unstake_position(nonce, &amount)
+            .unwrap_or_else(|| sc_panic!(ERROR_UNKNOWN_UNSTAKE_POSITION));
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        self.compute_early_withdraw_penalty(&unstake_token_attributes, current_epoch)
+    }
+
+    // Same JSON document that was attached to the unstake token as a data URI, except that per-epoch tokens
+    // report the amounts of every unstake minted on the nonce instead of zero
+    #[view(getUnstakeTokenMetadata)]
+    fn get_unstake_token_metadata(&self, nonce: u64) -> ManagedBuffer {
+        let unstake_token_attributes = self
+            .try_get_unstake_token_attributes(nonce)
+            .unwrap_or_else(|| sc_panic!(ERROR_UNKNOWN_UNSTAKE_POSITION));
+
+        self.build_unstake_token_metadata(&unstake_token_attributes)
+    }
+
+    // Unstake token nonces with the quantity held, which is ignored for per-position tokens
+    #[view(getUnstakePositionInfo)]
+    fn get_unstake_position_info(
+        &self,
+        positions: MultiValueEncoded<MultiValue2<u64, BigUint>>,
+    ) -> MultiValueEncoded<UnstakePositionInfo<Self::Api>> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        let is_active = self.is_state_active(storage_cache.contract_state());
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unstake_value_index = self.get_unstake_value_index();
+
+        let mut positions_info = MultiValueEncoded::new();
+        for position in positions.into_iter() {
+            let (nonce, quantity) = position.into_tuple();
+            let attributes = self
+                .try_get_unstake_position(nonce, &quantity)
+                .unwrap_or_else(|| sc_panic!(ERROR_UNKNOWN_UNSTAKE_POSITION));
+            let current_value = self.compute_unstake_value(&attributes);
+            let haircut_amount = if current_value < attributes.original_amount {
+                &attributes.original_amount - &current_value
+            } else {
+                BigUint::zero()
+            };
+            let haircut = if unstake_value_index < attributes.unstake_value_index {
+                ((&attributes.unstake_value_index - &unstake_value_index) * MAX_PERCENTAGE
+                    / &attributes.unstake_value_index)
+                    .to_u64()
+                    .unwrap_or_default()
+            } else {
+                0
+            };
+
+            positions_info.push(UnstakePositionInfo {
+                nonce,
+                original_amount: attributes.original_amount,
+                share_amount: attributes.share_amount,
+                current_value,
+                haircut_amount,
+                haircut,
+                unstake_epoch: attributes.unstake_epoch,
+                unbond_epoch: attributes.unbond_epoch,
+                epochs_remaining: attributes.unbond_epoch.saturating_sub(current_epoch),
+                tier_id: attributes.tier_id,
+                withdrawable: is_active && current_epoch >= attributes.unbond_epoch,
+            });
+        }
+
+        positions_info
+    }
+
+    // Returns nothing when the total reserve is uncapped
+    #[view(getRemainingTotalCapacity)]
+    fn get_remaining_total_capacity(&self) -> OptionalValue<BigUint> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        self.compute_remaining_total_capacity(storage_cache.virtual_xoxno_reserve())
+            .into()
+    }
+
+    // Returns nothing when the delegation per address is uncapped
+    #[view(getRemainingAddressCapacity)]
+    fn get_remaining_address_capacity(&self, address: ManagedAddress) -> OptionalValue<BigUint> {
+        self.compute_remaining_address_capacity(&address).into()
+    }
+
+    // Returns nothing when the unbond amount per epoch is unlimited
+    #[view(getRemainingUnbondCapacity)]
+    fn get_remaining_unbond_capacity(&self, epoch: u64) -> OptionalValue<BigUint> {
+        self.compute_remaining_unbond_capacity(epoch).into()
+    }
+
+    // Number of full epochs a new unbond request of the given tier would be delayed by
+    #[view(getUnbondQueueDepth)]
+    fn get_unbond_queue_depth(&self, tier_id: OptionalValue<u8>) -> u64 {
+        let tier_id = tier_id.into_option().unwrap_or(DEFAULT_UNBOND_TIER);
+        let tier = self.get_unbond_tier(tier_id);
+        let first_epoch = self.blockchain().get_block_epoch() + tier.unbond_epochs;
+        if self.max_unbond_per_epoch().is_empty() {
+            return 0;
+        }
+
+        let max_unbond = self.max_unbond_per_epoch().get();
+        self.first_open_unbond_epoch(first_epoch, &max_unbond) - first_epoch
     }
 
     #[view(getExchangeRate)]
     fn get_exchange_rate(&self) -> BigUint {
         let storage_cache = ReadOnlyStorageCache::new(self);
+        self.compute_exchange_rate(&storage_cache)
+    }
+
+    #[view(getContractSummary)]
+    fn get_contract_summary(&self) -> ContractSummary<Self::Api> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        let ls_token_id = match self.ls_token().get_token_state() {
+            TokenMapperState::Token(token_id) => Some(token_id),
+            _ => None,
+        };
+        let unstake_token_id = match self.unstake_token().get_token_state() {
+            TokenMapperState::Token(token_id) => Some(token_id),
+            _ => None,
+        };
+        let contract_balance = self.blockchain().get_sc_balance(
+            &EgldOrEsdtTokenIdentifier::esdt(storage_cache.main_token_id().clone()),
+            0,
+        );
 
-        // The initial exchange rate between XOXNO and LXOXNO is fixed to one
-        if storage_cache.ls_token_supply.clone() == BigUint::zero() {
-            return BigUint::from(INITIAL_EXCHANGE_RATE);
+        ContractSummary {
+            state: storage_cache.contract_state(),
+            main_token_id: storage_cache.main_token_id().clone(),
+            ls_token_id,
+            unstake_token_id,
+            unstake_token_mode: self.unstake_token_mode().get(),
+            ls_token_supply: storage_cache.ls_token_supply().clone(),
+            virtual_xoxno_reserve: storage_cache.virtual_xoxno_reserve().clone(),
+            unstake_token_supply: storage_cache.total_unstaked_xoxno().clone(),
+            rounding_dust: self.rounding_dust().get(),
+            exchange_rate: self.compute_exchange_rate(&storage_cache),
+            unbond_period: self.get_unbond_tier(DEFAULT_UNBOND_TIER).unbond_epochs,
+            current_epoch: self.blockchain().get_block_epoch(),
+            contract_balance,
         }
+    }
+
+    // Reserve and supply the exchange rate is derived from, one to one while no LS token exists
+    #[view(getExchangeRateFraction)]
+    fn get_exchange_rate_fraction(&self) -> MultiValue2<BigUint, BigUint> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        pricing::exchange_rate_fraction(&storage_cache).into()
+    }
 
-        storage_cache.virtual_xoxno_reserve.clone() * BigUint::from(INITIAL_EXCHANGE_RATE)
-            / storage_cache.ls_token_supply.clone()
+    #[view(getExchangeRateWithPrecision)]
+    fn get_exchange_rate_with_precision(&self, decimals: u32) -> BigUint {
+        require!(
+            decimals <= MAX_EXCHANGE_RATE_DECIMALS,
+            ERROR_INVALID_PRECISION
+        );
+
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        pricing::exchange_rate_with_precision(&storage_cache, decimals)
     }
 }
@@ -2,36 +2,117 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::contexts::base::StorageCache;
-use crate::contexts::readonly::ReadOnlyStorageCache;
 use crate::errors::*;
+use crate::pricing::{self, PoolState, Undelegation};
 
 use super::config;
+use crate::config::{
+    EpochUnstakeNonce, UnstakeTokenAttributes, UnstakeTokenMode, MAX_NAME_TEMPLATE_LENGTH,
+    MAX_PERCENTAGE,
+};
 
 pub const UNDELEGATE_TOKEN_URI: &[u8] =
     b"https://ipfs.io/ipfs/QmY4jtQh6M24uAFR3LcyV7QmL8pkL6zFxXyPXBuzo5sdX5";
+pub const UNSTAKE_TOKEN_METADATA_URI_PREFIX: &[u8] = b"data:application/json,";
+pub const NAME_TEMPLATE_AMOUNT: &[u8] = b"{amount}";
+pub const NAME_TEMPLATE_EPOCH: &[u8] = b"{epoch}";
 
 #[type_abi]
-#[derive(TopEncode, TopDecode, PartialEq, Eq, Copy, Clone, Debug)]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum State {
     Inactive,
     Active,
+    Emergency,
 }
 
 #[multiversx_sc::module]
 pub trait LiquidityPoolModule:
-    config::ConfigModule + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::stats::StatsModule
+    + crate::reports::ReportsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
 {
+    // Every delegation path goes through here, so the total cap, the stats and the epoch report see all of them
+    // The beneficiaries are tracked by the callers, a split delegation has several of them
+    fn process_delegation(
+        &self,
+        staked_amount: &BigUint,
+        storage_cache: &mut StorageCache<Self>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        self.require_within_total_cap(staked_amount, storage_cache);
+
+        let ls_token_amount = self.pool_add_liquidity(staked_amount, storage_cache);
+        self.record_delegation(staked_amount);
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.update_epoch_report(current_epoch, storage_cache, |report| {
+            report.delegated += staked_amount;
+        });
+
+        self.mint_ls_token(ls_token_amount)
+    }
+
     fn pool_add_liquidity(
         &self,
         token_amount: &BigUint,
         storage_cache: &mut StorageCache<Self>,
     ) -> BigUint {
-        let ls_amount = self.get_ls_token_amount(token_amount, &storage_cache);
+        let delegation = pricing::apply_delegation(token_amount, storage_cache)
+            .unwrap_or_else(|error| sc_panic!(error));
+
+        let dust = delegation.rounding_dust + delegation.orphaned_reserve;
+        if dust > 0 {
+            self.rounding_dust()
+                .update(|rounding_dust| *rounding_dust += dust);
+        }
+
+        delegation.ls_token_amount
+    }
+
+    fn require_within_total_cap(&self, token_amount: &BigUint, storage_cache: &StorageCache<Self>) {
+        if let Some(remaining) =
+            self.compute_remaining_total_capacity(storage_cache.virtual_xoxno_reserve())
+        {
+            require!(token_amount <= &remaining, ERROR_TOTAL_CAP_EXCEEDED);
+        }
+    }
+
+    // The delegated amount is tracked per beneficiary even when no cap is set, so a cap can be enabled later
+    fn track_beneficiary_delegation(&self, beneficiary: &ManagedAddress, token_amount: &BigUint) {
+        if let Some(remaining) = self.compute_remaining_address_capacity(beneficiary) {
+            require!(token_amount <= &remaining, ERROR_ADDRESS_CAP_EXCEEDED);
+        }
+
+        self.record_beneficiary(beneficiary);
+        self.delegated_amount(beneficiary)
+            .update(|delegated| *delegated += token_amount);
+    }
+
+    fn compute_remaining_total_capacity(&self, virtual_xoxno_reserve: &BigUint) -> Option<BigUint> {
+        if self.max_total_reserve().is_empty() {
+            return None;
+        }
 
-        storage_cache.ls_token_supply += &ls_amount;
-        storage_cache.virtual_xoxno_reserve += token_amount;
+        let max_reserve = self.max_total_reserve().get();
+        if virtual_xoxno_reserve >= &max_reserve {
+            Some(BigUint::zero())
+        } else {
+            Some(max_reserve - virtual_xoxno_reserve)
+        }
+    }
 
-        ls_amount
+    fn compute_remaining_address_capacity(&self, address: &ManagedAddress) -> Option<BigUint> {
+        if self.max_delegation_per_address().is_empty() {
+            return None;
+        }
+
+        let max_delegation = self.max_delegation_per_address().get();
+        let delegated = self.delegated_amount(address).get();
+        if delegated >= max_delegation {
+            Some(BigUint::zero())
+        } else {
+            Some(max_delegation - delegated)
+        }
     }
 
     fn pool_remove_liquidity(
@@ -39,117 +120,474 @@ pub trait LiquidityPoolModule:
         token_amount: &BigUint,
         storage_cache: &mut StorageCache<Self>,
     ) -> BigUint {
-        let xoxno_amount = self.get_xoxno_amount(token_amount, storage_cache);
-        storage_cache.ls_token_supply -= token_amount;
-        storage_cache.virtual_xoxno_reserve -= &xoxno_amount;
+        pricing::remove_liquidity(token_amount, storage_cache)
+            .unwrap_or_else(|error| sc_panic!(error))
+    }
 
-        xoxno_amount
+    fn pool_undelegate(
+        &self,
+        token_amount: &BigUint,
+        tier_fee: u64,
+        storage_cache: &mut StorageCache<Self>,
+    ) -> Undelegation<Self::Api> {
+        pricing::apply_undelegation(token_amount, tier_fee, storage_cache)
+            .unwrap_or_else(|error| sc_panic!(error))
     }
 
-    fn get_xoxno_amount(
+    fn pool_release_unstaked(
         &self,
-        ls_token_amount: &BigUint,
-        storage_cache: &StorageCache<Self>,
-    ) -> BigUint {
+        unstake_amount: &BigUint,
+        storage_cache: &mut StorageCache<Self>,
+    ) {
+        // Handle the case when the user tries to withdraw more than the total unstaked amount (in case of the last user withdrawal)
+        if unstake_amount > storage_cache.total_unstaked_xoxno() {
+            *storage_cache.total_unstaked_xoxno_mut() = BigUint::from(0u64);
+        } else {
+            *storage_cache.total_unstaked_xoxno_mut() -= unstake_amount;
+        }
+    }
+
+    // Queues the amount into the first epoch, starting from min_epoch, that still has enough unbond capacity
+    fn schedule_unbond(&self, amount: &BigUint, min_epoch: u64) -> u64 {
+        let unbond_epoch = self
+            .find_unbond_epoch(amount, min_epoch)
+            .unwrap_or_else(|| sc_panic!(ERROR_UNBOND_LIMIT_EXCEEDED));
+        self.scheduled_unbond(unbond_epoch)
+            .update(|scheduled| *scheduled += amount);
+
+        if !self.max_unbond_per_epoch().is_empty() {
+            let max_unbond = self.max_unbond_per_epoch().get();
+            self.update_next_open_unbond_epoch(unbond_epoch, &max_unbond);
+            self.update_next_open_unbond_epoch(min_epoch, &max_unbond);
+        }
+
+        unbond_epoch
+    }
+
+    // None when the amount is above the unbond limit of a single epoch
+    fn find_unbond_epoch(&self, amount: &BigUint, min_epoch: u64) -> Option<u64> {
+        if self.max_unbond_per_epoch().is_empty() {
+            return Some(min_epoch);
+        }
+
+        let max_unbond = self.max_unbond_per_epoch().get();
+        if amount > &max_unbond {
+            return None;
+        }
+
+        let mut unbond_epoch = self.first_open_unbond_epoch(min_epoch, &max_unbond);
+        while self.scheduled_unbond(unbond_epoch).get() + amount > max_unbond {
+            unbond_epoch = self.first_open_unbond_epoch(unbond_epoch + 1, &max_unbond);
+        }
+
+        Some(unbond_epoch)
+    }
+
+    // Dust positions are paid out right away instead of minting an unstake token for them
+    // They count against the unbond limit of the current epoch, so a large exit split into small ones still waits in the queue
+    fn is_instant_withdraw(&self, amount: &BigUint, current_epoch: u64) -> bool {
+        amount < &self.instant_withdraw_threshold().get()
+            && self.find_unbond_epoch(amount, current_epoch) == Some(current_epoch)
+    }
+
+    // Jumps over the full epochs through their stored cursors instead of reading them one by one
+    fn first_open_unbond_epoch(&self, epoch: u64, max_unbond: &BigUint) -> u64 {
+        let cursor_generation = self.unbond_cursor_generation().get();
+        let mut epoch = epoch;
+        while &self.scheduled_unbond(epoch).get() >= max_unbond {
+            let next_open_epoch_mapper = self.next_open_unbond_epoch(cursor_generation, epoch);
+            epoch = if next_open_epoch_mapper.is_empty() {
+                epoch + 1
+            } else {
+                next_open_epoch_mapper.get()
+            };
+        }
+
+        epoch
+    }
+
+    fn update_next_open_unbond_epoch(&self, epoch: u64, max_unbond: &BigUint) {
+        if &self.scheduled_unbond(epoch).get() >= max_unbond {
+            let next_open_epoch = self.first_open_unbond_epoch(epoch + 1, max_unbond);
+            self.next_open_unbond_epoch(self.unbond_cursor_generation().get(), epoch)
+                .set(next_open_epoch);
+        }
+    }
+
+    // Frees the share of the unbond limit of a position that leaves the queue before its unbond epoch
+    fn release_scheduled_unbond(&self, amount: &BigUint, unbond_epoch: u64) {
+        self.scheduled_unbond(unbond_epoch).update(|scheduled| {
+            // Positions unstaked before the schedule was tracked were never added to it
+            if &*scheduled > amount {
+                *scheduled -= amount;
+            } else {
+                *scheduled = BigUint::zero();
+            }
+        });
+
+        if !self.max_unbond_per_epoch().is_empty() {
+            let max_unbond = self.max_unbond_per_epoch().get();
+            self.repoint_next_open_unbond_epochs(unbond_epoch, &max_unbond);
+        }
+    }
+
+    // The full epochs right before an epoch that has capacity again may point past it, they are pointed back at it
+    // The walk stops at the first epoch with capacity left, since the cursors before it never point past it
+    fn repoint_next_open_unbond_epochs(&self, open_epoch: u64, max_unbond: &BigUint) {
+        if &self.scheduled_unbond(open_epoch).get() >= max_unbond {
+            return;
+        }
+
+        let cursor_generation = self.unbond_cursor_generation().get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut epoch = open_epoch;
+        while epoch > current_epoch {
+            epoch -= 1;
+            if &self.scheduled_unbond(epoch).get() < max_unbond {
+                break;
+            }
+
+            let next_open_epoch_mapper = self.next_open_unbond_epoch(cursor_generation, epoch);
+            if !next_open_epoch_mapper.is_empty() && next_open_epoch_mapper.get() > open_epoch {
+                next_open_epoch_mapper.set(open_epoch);
+            }
+        }
+    }
+
+    fn compute_remaining_unbond_capacity(&self, epoch: u64) -> Option<BigUint> {
+        if self.max_unbond_per_epoch().is_empty() {
+            return None;
+        }
+
+        let max_unbond = self.max_unbond_per_epoch().get();
+        let scheduled = self.scheduled_unbond(epoch).get();
+        if scheduled >= max_unbond {
+            Some(BigUint::zero())
+        } else {
+            Some(max_unbond - scheduled)
+        }
+    }
+
+    // Splits the loss pro-rata between the LS token reserve and, optionally, the pending unstakes
+    fn pool_apply_loss(
+        &self,
+        loss_amount: &BigUint,
+        include_pending: bool,
+        storage_cache: &mut StorageCache<Self>,
+    ) -> (BigUint, BigUint) {
+        let mut affected_amount = storage_cache.virtual_xoxno_reserve().clone();
+        if include_pending {
+            affected_amount += storage_cache.total_unstaked_xoxno();
+        }
+        require!(affected_amount > 0, ERROR_NOTHING_TO_SLASH);
         require!(
-            &storage_cache.ls_token_supply >= ls_token_amount,
-            ERROR_NOT_ENOUGH_LP
+            loss_amount * MAX_PERCENTAGE <= &affected_amount * self.get_max_loss_per_call(),
+            ERROR_LOSS_TOO_HIGH
         );
 
-        let xoxno_amount =
-            ls_token_amount * &storage_cache.virtual_xoxno_reserve / &storage_cache.ls_token_supply;
-        require!(xoxno_amount > 0u64, ERROR_INSUFFICIENT_LIQ_BURNED);
+        let pending_loss = if include_pending {
+            loss_amount * storage_cache.total_unstaked_xoxno() / &affected_amount
+        } else {
+            BigUint::zero()
+        };
+        let reserve_loss = loss_amount - &pending_loss;
+
+        if pending_loss > 0 {
+            let remaining_pending = storage_cache.total_unstaked_xoxno() - &pending_loss;
+            let unstake_value_index = self.get_unstake_value_index() * &remaining_pending
+                / storage_cache.total_unstaked_xoxno();
+            self.unstake_value_index().set(unstake_value_index);
+            *storage_cache.total_unstaked_xoxno_mut() = remaining_pending;
+        }
+        *storage_cache.virtual_xoxno_reserve_mut() -= &reserve_loss;
+
+        (reserve_loss, pending_loss)
+    }
+
+    // The XOXNO amount an unstake token is worth after the losses applied since it was minted
+    fn compute_unstake_value(&self, attributes: &UnstakeTokenAttributes<Self::Api>) -> BigUint {
+        &attributes.original_amount * &self.get_unstake_value_index()
+            / &attributes.unstake_value_index
+    }
 
-        xoxno_amount
+    fn compute_exchange_rate(&self, pool: &impl PoolState<Self::Api>) -> BigUint {
+        pricing::exchange_rate(pool)
     }
 
-    fn get_xoxno_amount_read_only(
+    fn compute_early_withdraw_penalty(
         &self,
-        ls_token_amount: &BigUint,
-        storage_cache: &ReadOnlyStorageCache<Self>,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+        current_epoch: u64,
     ) -> BigUint {
-        require!(
-            &storage_cache.ls_token_supply >= ls_token_amount,
-            ERROR_NOT_ENOUGH_LP
-        );
+        if current_epoch >= attributes.unbond_epoch
+            || attributes.unbond_epoch <= attributes.unstake_epoch
+        {
+            return BigUint::zero();
+        }
+
+        let remaining_epochs = attributes.unbond_epoch - current_epoch;
+        let unbond_period = attributes.unbond_epoch - attributes.unstake_epoch;
+        let max_penalty = self.early_withdraw_max_penalty().get();
 
-        let xoxno_amount =
-            ls_token_amount * &storage_cache.virtual_xoxno_reserve / &storage_cache.ls_token_supply;
-        require!(xoxno_amount > 0u64, ERROR_INSUFFICIENT_LIQ_BURNED);
+        pricing::mul_div_ceil(
+            &self.compute_unstake_value(attributes),
+            &(BigUint::from(max_penalty) * remaining_epochs),
+            &(BigUint::from(MAX_PERCENTAGE) * unbond_period),
+        )
+    }
 
-        xoxno_amount
+    fn get_xoxno_amount(
+        &self,
+        ls_token_amount: &BigUint,
+        pool: &impl PoolState<Self::Api>,
+    ) -> BigUint {
+        pricing::xoxno_amount_for_ls_token(ls_token_amount, pool)
+            .unwrap_or_else(|error| sc_panic!(error))
     }
 
     fn get_ls_token_amount(
         &self,
         token_amount: &BigUint,
-        storage_cache: &StorageCache<Self>,
+        pool: &impl PoolState<Self::Api>,
     ) -> BigUint {
-        // Ensure the provided token_amount is greater than 0
-        require!(
-            token_amount > &BigUint::from(0u64),
-            ERROR_INSUFFICIENT_LIQ_BURNED
+        pricing::ls_token_amount_for_xoxno(token_amount, pool)
+            .unwrap_or_else(|error| sc_panic!(error))
+    }
+
+    fn mint_ls_token(&self, amount: BigUint) -> EsdtTokenPayment<Self::Api> {
+        self.ls_token().mint(amount)
+    }
+
+    fn burn_ls_token(&self, amount: &BigUint) {
+        self.ls_token().burn(amount);
+    }
+
+    fn mint_unstake_tokens(
+        &self,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        self.outstanding_unstake_amount()
+            .update(|outstanding| *outstanding += &attributes.original_amount);
+
+        match self.unstake_token_mode().get() {
+            UnstakeTokenMode::PerPosition => {
+                let payment = self.create_unstake_token(&BigUint::from(1u64), attributes);
+                self.unstake_positions(&payment.token_identifier, payment.token_nonce)
+                    .set(attributes);
+                payment
+            }
+            UnstakeTokenMode::PerEpoch => self.mint_epoch_unstake_tokens(attributes),
+        }
+    }
+
+    // Unstakes maturing in the same epoch share one nonce as long as no loss changed the value index
+    // Only unstakes from the same epoch and tier share it, so every holder gets the early withdraw penalty of their own unstake
+    // The stored position adds up the amounts of every unstake minted on the nonce
+    fn mint_epoch_unstake_tokens(
+        &self,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let token_id = self.unstake_token().get_token_id();
+        let epoch_nonce_mapper = self.epoch_unstake_nonce(
+            &token_id,
+            attributes.unbond_epoch,
+            attributes.unstake_epoch,
+            attributes.tier_id,
         );
+        if !epoch_nonce_mapper.is_empty() {
+            let epoch_nonce = epoch_nonce_mapper.get();
+            if epoch_nonce.unstake_value_index == attributes.unstake_value_index {
+                let position_mapper = self.unstake_positions(&token_id, epoch_nonce.nonce);
+                if !position_mapper.is_empty() {
+                    position_mapper.update(|position| {
+                        position.original_amount += &attributes.original_amount;
+                        position.share_amount += &attributes.share_amount;
+                    });
+                }
 
-        // Calculate the ls_token_amount based on the provided token_amount and including rewards
-        let ls_token_amount = if storage_cache.virtual_xoxno_reserve > 0 {
-            token_amount.clone() * &storage_cache.ls_token_supply
-                / &storage_cache.virtual_xoxno_reserve
-        } else {
-            token_amount.clone()
-        };
+                return self
+                    .unstake_token()
+                    .nft_add_quantity(epoch_nonce.nonce, attributes.original_amount.clone());
+            }
+        }
 
-        // Ensure that the calculated ls_token_amount is greater than zero
-        require!(ls_token_amount > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+        // The attributes describe the epoch, the amounts are carried by the token quantity
+        let epoch_attributes = UnstakeTokenAttributes {
+            original_amount: BigUint::zero(),
+            share_amount: BigUint::zero(),
+            unstake_epoch: attributes.unstake_epoch,
+            unbond_epoch: attributes.unbond_epoch,
+            tier_id: attributes.tier_id,
+            unstake_value_index: attributes.unstake_value_index.clone(),
+        };
+        let payment = self.create_unstake_token(&attributes.original_amount, &epoch_attributes);
+        self.unstake_positions(&token_id, payment.token_nonce)
+            .set(attributes);
+        epoch_nonce_mapper.set(EpochUnstakeNonce {
+            nonce: payment.token_nonce,
+            unstake_value_index: attributes.unstake_value_index.clone(),
+        });
+        self.epoch_unstake_nonces(&payment.token_identifier, attributes.unbond_epoch)
+            .push(&payment.token_nonce);
 
-        ls_token_amount
+        payment
     }
 
-    fn get_ls_token_amount_readonly(
+    fn create_unstake_token(
         &self,
-        token_amount: &BigUint,
-        storage_cache: &ReadOnlyStorageCache<Self>,
-    ) -> BigUint {
-        // Ensure the provided token_amount is greater than 0
-        require!(
-            token_amount > &BigUint::from(0u64),
-            ERROR_INSUFFICIENT_LIQ_BURNED
+        amount: &BigUint,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let token_id = self.unstake_token().get_token_id();
+        let name =
+            self.build_unstake_token_name(&attributes.original_amount, attributes.unbond_epoch);
+
+        // Marketplaces render the JSON copy, the attributes keep the binary layout read by the contract
+        let mut uris = self.get_unstake_token_uris();
+        let mut metadata_uri = ManagedBuffer::from(UNSTAKE_TOKEN_METADATA_URI_PREFIX);
+        metadata_uri.append(&self.build_unstake_token_metadata(attributes));
+        uris.push(metadata_uri);
+
+        let token_nonce = self.send().esdt_nft_create(
+            &token_id,
+            amount,
+            &name,
+            &BigUint::zero(),
+            &ManagedBuffer::new(),
+            attributes,
+            &uris,
         );
 
-        // Calculate the ls_token_amount based on the provided token_amount and including rewards
-        let ls_token_amount = if storage_cache.virtual_xoxno_reserve > 0 {
-            token_amount.clone() * &storage_cache.ls_token_supply
-                / &storage_cache.virtual_xoxno_reserve
-        } else {
-            token_amount.clone()
+        EsdtTokenPayment::new(token_id, token_nonce, amount.clone())
+    }
+
+    // Attributes of the unstaked position carried by the given quantity of an unstake token
+    fn get_unstake_position(
+        &self,
+        token_nonce: u64,
+        amount: &BigUint,
+    ) -> UnstakeTokenAttributes<Self::Api> {
+        // Nonces minted before the positions were stored are read from the token, the contract holds the payment
+        let attributes = self
+            .try_get_unstake_token_attributes(token_nonce)
+            .unwrap_or_else(|| self.unstake_token().get_token_attributes(token_nonce));
+        self.with_position_amount(attributes, amount)
+    }
+
+    // Same as get_unstake_position, without reverting for an unknown nonce or an unissued unstake token
+    fn try_get_unstake_position(
+        &self,
+        token_nonce: u64,
+        amount: &BigUint,
+    ) -> Option<UnstakeTokenAttributes<Self::Api>> {
+        self.try_get_unstake_token_attributes(token_nonce)
+            .map(|attributes| self.with_position_amount(attributes, amount))
+    }
+
+    fn try_get_unstake_token_attributes(
+        &self,
+        token_nonce: u64,
+    ) -> Option<UnstakeTokenAttributes<Self::Api>> {
+        let TokenMapperState::Token(token_id) = self.unstake_token().get_token_state() else {
+            return None;
         };
 
-        // Ensure that the calculated ls_token_amount is greater than zero
-        require!(ls_token_amount > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+        let position_mapper = self.unstake_positions(&token_id, token_nonce);
+        if position_mapper.is_empty() {
+            return None;
+        }
 
-        ls_token_amount
+        Some(position_mapper.get())
     }
 
-    fn mint_ls_token(&self, amount: BigUint) -> EsdtTokenPayment<Self::Api> {
-        self.ls_token().mint(amount)
+    fn with_position_amount(
+        &self,
+        mut attributes: UnstakeTokenAttributes<Self::Api>,
+        amount: &BigUint,
+    ) -> UnstakeTokenAttributes<Self::Api> {
+        if self.unstake_token_mode().get() == UnstakeTokenMode::PerEpoch {
+            // Stored per-epoch positions hold the totals minted on the nonce, the shares are split pro-rata
+            if attributes.original_amount > 0 {
+                attributes.share_amount =
+                    &attributes.share_amount * amount / &attributes.original_amount;
+            }
+            attributes.original_amount = amount.clone();
+        }
+
+        attributes
     }
 
-    fn burn_ls_token(&self, amount: &BigUint) {
-        self.ls_token().burn(amount);
+    fn build_unstake_token_name(&self, amount: &BigUint, unbond_epoch: u64) -> ManagedBuffer {
+        let template = self.get_unstake_token_name_template();
+        let mut template_buffer = [0u8; MAX_NAME_TEMPLATE_LENGTH];
+        let template_bytes = template.load_to_byte_array(&mut template_buffer);
+
+        let mut name = ManagedBuffer::new();
+        let mut literal_start = 0;
+        let mut index = 0;
+        while index < template_bytes.len() {
+            let remaining = &template_bytes[index..];
+            let (placeholder_len, value) = if remaining.starts_with(NAME_TEMPLATE_AMOUNT) {
+                (NAME_TEMPLATE_AMOUNT.len(), amount.to_display())
+            } else if remaining.starts_with(NAME_TEMPLATE_EPOCH) {
+                (
+                    NAME_TEMPLATE_EPOCH.len(),
+                    BigUint::from(unbond_epoch).to_display(),
+                )
+            } else {
+                index += 1;
+                continue;
+            };
+
+            name.append_bytes(&template_bytes[literal_start..index]);
+            name.append(&value);
+            index += placeholder_len;
+            literal_start = index;
+        }
+        name.append_bytes(&template_bytes[literal_start..]);
+
+        name
     }
 
-    fn mint_unstake_tokens<T: TopEncode>(&self, attributes: &T) -> EsdtTokenPayment<Self::Api> {
-        let token_map = self.unstake_token();
-        let nft = token_map.nft_create(BigUint::from(1u64), attributes);
+    fn build_unstake_token_metadata(
+        &self,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+    ) -> ManagedBuffer {
+        let mut metadata = ManagedBuffer::from(b"{\"originalAmount\":\"");
+        metadata.append(&attributes.original_amount.to_display());
+        metadata.append_bytes(b"\",\"shareAmount\":\"");
+        metadata.append(&attributes.share_amount.to_display());
+        metadata.append_bytes(b"\",\"unstakeEpoch\":");
+        metadata.append(&BigUint::from(attributes.unstake_epoch).to_display());
+        metadata.append_bytes(b",\"unbondEpoch\":");
+        metadata.append(&BigUint::from(attributes.unbond_epoch).to_display());
+        metadata.append_bytes(b",\"tierId\":");
+        metadata.append(&BigUint::from(attributes.tier_id).to_display());
+        metadata.append_bytes(b"}");
 
-        let uri = ManagedBuffer::from(UNDELEGATE_TOKEN_URI);
-        self.send()
-            .nft_add_uri(token_map.get_token_id_ref(), nft.token_nonce, uri);
-        nft
+        metadata
     }
 
-    fn burn_unstake_tokens(&self, token_nonce: u64) {
-        self.unstake_token()
-            .nft_burn(token_nonce, &BigUint::from(1u64));
+    // The attributes are the ones of the burned quantity, as returned by get_unstake_position
+    fn burn_unstake_tokens(
+        &self,
+        token_nonce: u64,
+        amount: &BigUint,
+        attributes: &UnstakeTokenAttributes<Self::Api>,
+    ) {
+        self.unstake_token().nft_burn(token_nonce, amount);
+        // A per-position nonce is gone once burned, a per-epoch one may still be held by others
+        if self.unstake_token_mode().get() == UnstakeTokenMode::PerPosition {
+            self.unstake_positions(&self.unstake_token().get_token_id(), token_nonce)
+                .clear();
+        }
+        self.outstanding_unstake_amount().update(|outstanding| {
+            // Tokens minted before the amount was tracked are only counted by the storage migration
+            if *outstanding > attributes.original_amount {
+                *outstanding -= &attributes.original_amount;
+            } else {
+                *outstanding = BigUint::zero();
+            }
+        });
     }
 }
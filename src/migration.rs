@@ -0,0 +1,80 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use multiversx_sc_modules::ongoing_operation::{
+    CONTINUE_OP, DEFAULT_MIN_GAS_TO_SAVE_PROGRESS, STOP_OP,
+};
+
+use crate::config::{DEFAULT_MAX_LOSS_PER_CALL, INITIAL_EXCHANGE_RATE};
+use crate::errors::*;
+
+pub const CURRENT_STORAGE_VERSION: u32 = 3;
+
+// The progress is kept in the storage version and the migration cursor, never in the ongoing operation
+// shared with the other long running endpoints, so neither can block or overwrite the other
+#[multiversx_sc::module]
+pub trait MigrationModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // Resumes a storage migration that ran out of gas during the upgrade
+    #[endpoint(migrateStorage)]
+    fn migrate_storage(&self) -> OperationCompletionStatus {
+        self.require_owner();
+        self.run_storage_migrations()
+    }
+
+    fn run_storage_migrations(&self) -> OperationCompletionStatus {
+        self.run_while_it_has_gas(DEFAULT_MIN_GAS_TO_SAVE_PROGRESS, || {
+            let from_version = self.storage_version().get();
+            if from_version >= CURRENT_STORAGE_VERSION {
+                return STOP_OP;
+            }
+
+            if self.migrate_storage_step(from_version) {
+                self.storage_migration_cursor().clear();
+                self.storage_version().set(from_version + 1);
+            }
+
+            CONTINUE_OP
+        })
+    }
+
+    // Migrates a chunk of the storage from the given version, returns true once the next version is reached
+    // Steps that walk a collection keep their position in the migration cursor between chunks
+    fn migrate_storage_step(&self, from_version: u32) -> bool {
+        match from_version {
+            0 => {
+                self.unstake_value_index()
+                    .set_if_empty(BigUint::from(INITIAL_EXCHANGE_RATE));
+                self.max_loss_per_call()
+                    .set_if_empty(DEFAULT_MAX_LOSS_PER_CALL);
+                true
+            }
+            1 => {
+                // Pin the admin role to the protocol owner at the time of the upgrade
+                self.contract_owner()
+                    .set_if_empty(self.blockchain().get_owner_address());
+                true
+            }
+            2 => {
+                // No loss could be applied before the storage versions, so the unstake supply is still the original amount
+                self.outstanding_unstake_amount()
+                    .set_if_empty(self.unstake_token_supply().get());
+                true
+            }
+            _ => sc_panic!(ERROR_UNKNOWN_STORAGE_VERSION),
+        }
+    }
+
+    #[view(getStorageVersion)]
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
+    // Position of the current step within the collection it migrates, cleared whenever a version is reached
+    #[view(getStorageMigrationCursor)]
+    #[storage_mapper("storageMigrationCursor")]
+    fn storage_migration_cursor(&self) -> SingleValueMapper<u64>;
+}
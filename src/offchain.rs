@@ -0,0 +1,175 @@
+use num_bigint::BigUint;
+
+use crate::config::{INITIAL_EXCHANGE_RATE, MAX_PERCENTAGE};
+use crate::errors::*;
+
+// Plain Rust mirror of the pricing module over num-bigint, for backends and simulations
+// Every result must match the contract to the last unit, tests/pricing_tests.rs runs both side by side
+pub type OffchainPricingResult<T> = Result<T, &'static [u8]>;
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct OffchainPool {
+    pub ls_token_supply: BigUint,
+    pub virtual_xoxno_reserve: BigUint,
+    pub rounding_dust: BigUint,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Undelegation {
+    pub xoxno_removed: BigUint, // XOXNO taken out of the reserve for the burned LS tokens
+    pub fee_amount: BigUint,    // Part of the above paid back to the reserve as unbond tier fee
+    pub xoxno_to_unstake: BigUint, // XOXNO the user receives, instantly or through an unstake token
+}
+
+pub fn mul_div_floor(amount: &BigUint, numerator: &BigUint, denominator: &BigUint) -> BigUint {
+    amount * numerator / denominator
+}
+
+pub fn mul_div_ceil(amount: &BigUint, numerator: &BigUint, denominator: &BigUint) -> BigUint {
+    (amount * numerator + denominator - 1u32) / denominator
+}
+
+impl OffchainPool {
+    pub fn new(ls_token_supply: BigUint, virtual_xoxno_reserve: BigUint) -> Self {
+        OffchainPool {
+            ls_token_supply,
+            virtual_xoxno_reserve,
+            rounding_dust: BigUint::ZERO,
+        }
+    }
+
+    pub fn ls_token_amount_for_xoxno(
+        &self,
+        xoxno_amount: &BigUint,
+    ) -> OffchainPricingResult<BigUint> {
+        if *xoxno_amount == BigUint::ZERO {
+            return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+        }
+
+        let ls_token_amount =
+            if self.ls_token_supply > BigUint::ZERO && self.virtual_xoxno_reserve > BigUint::ZERO {
+                mul_div_floor(
+                    xoxno_amount,
+                    &self.ls_token_supply,
+                    &self.virtual_xoxno_reserve,
+                )
+            } else {
+                xoxno_amount.clone()
+            };
+        if ls_token_amount == BigUint::ZERO {
+            return Err(ERROR_INSUFFICIENT_LIQUIDITY);
+        }
+
+        Ok(ls_token_amount)
+    }
+
+    pub fn xoxno_amount_for_ls_token(
+        &self,
+        ls_token_amount: &BigUint,
+    ) -> OffchainPricingResult<BigUint> {
+        if self.ls_token_supply < *ls_token_amount {
+            return Err(ERROR_NOT_ENOUGH_LP);
+        }
+        if *ls_token_amount == BigUint::ZERO {
+            return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+        }
+
+        let xoxno_amount = mul_div_floor(
+            ls_token_amount,
+            &self.virtual_xoxno_reserve,
+            &self.ls_token_supply,
+        );
+        if xoxno_amount == BigUint::ZERO {
+            return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+        }
+
+        Ok(xoxno_amount)
+    }
+
+    pub fn delegation_dust(&self, xoxno_amount: &BigUint, ls_token_amount: &BigUint) -> BigUint {
+        if self.virtual_xoxno_reserve == BigUint::ZERO || self.ls_token_supply == BigUint::ZERO {
+            return BigUint::ZERO;
+        }
+
+        xoxno_amount
+            - mul_div_ceil(
+                ls_token_amount,
+                &self.virtual_xoxno_reserve,
+                &self.ls_token_supply,
+            )
+    }
+
+    pub fn exchange_rate_fraction(&self) -> (BigUint, BigUint) {
+        if self.ls_token_supply == BigUint::ZERO {
+            return (BigUint::from(1u32), BigUint::from(1u32));
+        }
+
+        (
+            self.virtual_xoxno_reserve.clone(),
+            self.ls_token_supply.clone(),
+        )
+    }
+
+    pub fn exchange_rate_with_precision(&self, decimals: u32) -> BigUint {
+        let (reserve, supply) = self.exchange_rate_fraction();
+        mul_div_floor(&reserve, &BigUint::from(10u32).pow(decimals), &supply)
+    }
+
+    // XOXNO per LS token, scaled by INITIAL_EXCHANGE_RATE
+    pub fn exchange_rate(&self) -> BigUint {
+        let (reserve, supply) = self.exchange_rate_fraction();
+        mul_div_floor(&reserve, &BigUint::from(INITIAL_EXCHANGE_RATE), &supply)
+    }
+
+    // Same pool changes as the delegate endpoint, returns the minted LS token amount
+    pub fn delegate(&mut self, xoxno_amount: &BigUint) -> OffchainPricingResult<BigUint> {
+        let ls_token_amount = self.ls_token_amount_for_xoxno(xoxno_amount)?;
+        if self.ls_token_supply == BigUint::ZERO {
+            self.rounding_dust += core::mem::take(&mut self.virtual_xoxno_reserve);
+        }
+        let dust = self.delegation_dust(xoxno_amount, &ls_token_amount);
+        self.ls_token_supply += &ls_token_amount;
+        self.virtual_xoxno_reserve += xoxno_amount - &dust;
+        self.rounding_dust += dust;
+
+        Ok(ls_token_amount)
+    }
+
+    // Same pool changes as the unDelegate endpoint, the tier fee is in basis points of MAX_PERCENTAGE
+    pub fn undelegate(
+        &mut self,
+        ls_token_amount: &BigUint,
+        tier_fee: u64,
+    ) -> OffchainPricingResult<Undelegation> {
+        let xoxno_removed = self.xoxno_amount_for_ls_token(ls_token_amount)?;
+        self.ls_token_supply -= ls_token_amount;
+        self.virtual_xoxno_reserve -= &xoxno_removed;
+
+        let fee_amount = mul_div_ceil(
+            &xoxno_removed,
+            &BigUint::from(tier_fee),
+            &BigUint::from(MAX_PERCENTAGE),
+        );
+        self.virtual_xoxno_reserve += &fee_amount;
+        let xoxno_to_unstake = &xoxno_removed - &fee_amount;
+
+        Ok(Undelegation {
+            xoxno_removed,
+            fee_amount,
+            xoxno_to_unstake,
+        })
+    }
+
+    pub fn add_rewards(&mut self, rewards_amount: &BigUint) {
+        self.virtual_xoxno_reserve += rewards_amount;
+    }
+
+    // Same as the sweepRoundingDust endpoint, returns the amount sent to the treasury
+    pub fn sweep_rounding_dust(&mut self) -> OffchainPricingResult<BigUint> {
+        if self.rounding_dust == BigUint::ZERO {
+            return Err(ERROR_NO_ROUNDING_DUST);
+        }
+
+        Ok(core::mem::take(&mut self.rounding_dust))
+    }
+}
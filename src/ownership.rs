@@ -0,0 +1,31 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+
+// Admin role enforced by the contract itself, handed over through the OwnershipTransferModule endpoints
+#[multiversx_sc::module]
+pub trait OwnershipModule {
+    fn require_owner(&self) {
+        require!(
+            self.blockchain().get_caller() == self.get_contract_owner(),
+            ERROR_ONLY_OWNER
+        );
+    }
+
+    // Contracts deployed before the ownership was tracked in storage fall back to the protocol owner
+    #[view(getContractOwner)]
+    fn get_contract_owner(&self) -> ManagedAddress {
+        if self.contract_owner().is_empty() {
+            self.blockchain().get_owner_address()
+        } else {
+            self.contract_owner().get()
+        }
+    }
+
+    #[storage_mapper("contractOwner")]
+    fn contract_owner(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPendingOwner)]
+    #[storage_mapper("pendingOwner")]
+    fn pending_owner(&self) -> SingleValueMapper<ManagedAddress>;
+}
@@ -0,0 +1,44 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+
+#[multiversx_sc::module]
+pub trait OwnershipTransferModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::events::EventsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // The new owner only takes over after accepting, so a wrong address can be cancelled
+    #[endpoint(proposeOwner)]
+    fn propose_owner(&self, new_owner: ManagedAddress) {
+        self.require_owner();
+        require!(!new_owner.is_zero(), ERROR_INVALID_ADDRESS);
+
+        self.pending_owner().set(&new_owner);
+        self.emit_ownership_proposed_event(&self.get_contract_owner(), &new_owner);
+    }
+
+    #[endpoint(acceptOwnership)]
+    fn accept_ownership(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.pending_owner().is_empty() && self.pending_owner().get() == caller,
+            ERROR_NOT_PENDING_OWNER
+        );
+
+        let previous_owner = self.get_contract_owner();
+        self.pending_owner().clear();
+        self.contract_owner().set(&caller);
+        self.emit_ownership_transferred_event(&previous_owner, &caller);
+    }
+
+    #[endpoint(cancelOwnershipProposal)]
+    fn cancel_ownership_proposal(&self) {
+        self.require_owner();
+        require!(!self.pending_owner().is_empty(), ERROR_NO_PENDING_OWNER);
+
+        let pending_owner = self.pending_owner().take();
+        self.emit_ownership_proposal_cancelled_event(&self.get_contract_owner(), &pending_owner);
+    }
+}
@@ -0,0 +1,245 @@
+multiversx_sc::imports!();
+
+use crate::config::{INITIAL_EXCHANGE_RATE, MAX_PERCENTAGE};
+use crate::errors::*;
+
+// The pool amounts the LS token price depends on, implemented by both storage caches
+pub trait PoolState<M: ManagedTypeApi> {
+    fn ls_token_supply(&self) -> &BigUint<M>;
+
+    fn virtual_xoxno_reserve(&self) -> &BigUint<M>;
+}
+
+// Implemented by the pools the delegation and undelegation transitions can be applied to
+pub trait PoolStateMut<M: ManagedTypeApi>: PoolState<M> {
+    fn ls_token_supply_mut(&mut self) -> &mut BigUint<M>;
+
+    fn virtual_xoxno_reserve_mut(&mut self) -> &mut BigUint<M>;
+}
+
+// Pool amounts held outside of the contract storage, e.g. to simulate a sequence of operations
+#[derive(Clone, PartialEq, Debug)]
+pub struct PoolSnapshot<M: ManagedTypeApi> {
+    pub ls_token_supply: BigUint<M>,
+    pub virtual_xoxno_reserve: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> PoolSnapshot<M> {
+    pub fn from_pool(pool: &impl PoolState<M>) -> Self {
+        PoolSnapshot {
+            ls_token_supply: pool.ls_token_supply().clone(),
+            virtual_xoxno_reserve: pool.virtual_xoxno_reserve().clone(),
+        }
+    }
+}
+
+impl<M: ManagedTypeApi> PoolState<M> for PoolSnapshot<M> {
+    fn ls_token_supply(&self) -> &BigUint<M> {
+        &self.ls_token_supply
+    }
+
+    fn virtual_xoxno_reserve(&self) -> &BigUint<M> {
+        &self.virtual_xoxno_reserve
+    }
+}
+
+impl<M: ManagedTypeApi> PoolStateMut<M> for PoolSnapshot<M> {
+    fn ls_token_supply_mut(&mut self) -> &mut BigUint<M> {
+        &mut self.ls_token_supply
+    }
+
+    fn virtual_xoxno_reserve_mut(&mut self) -> &mut BigUint<M> {
+        &mut self.virtual_xoxno_reserve
+    }
+}
+
+// Outcome of a delegation, neither the rounding dust nor the orphaned reserve back the LS token anymore
+#[derive(Clone, PartialEq, Debug)]
+pub struct Delegation<M: ManagedTypeApi> {
+    pub ls_token_amount: BigUint<M>,
+    pub rounding_dust: BigUint<M>,
+    pub orphaned_reserve: BigUint<M>,
+}
+
+// Outcome of an undelegation, the fee stays in the reserve
+#[derive(Clone, PartialEq, Debug)]
+pub struct Undelegation<M: ManagedTypeApi> {
+    pub xoxno_removed: BigUint<M>,
+    pub fee_amount: BigUint<M>,
+    pub xoxno_to_unstake: BigUint<M>,
+}
+
+// Errors are the contract error messages, the contract reverts with them as they are
+pub type PricingResult<M> = Result<BigUint<M>, &'static [u8]>;
+
+// Rounding always favours the pool: amounts minted or paid out are rounded down, amounts kept by the pool are rounded up
+pub fn mul_div_floor<M: ManagedTypeApi>(
+    amount: &BigUint<M>,
+    numerator: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    amount * numerator / denominator
+}
+
+pub fn mul_div_ceil<M: ManagedTypeApi>(
+    amount: &BigUint<M>,
+    numerator: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    (amount * numerator + denominator - 1u32) / denominator
+}
+
+// LS tokens minted for a delegated XOXNO amount, 1:1 while the supply or the reserve is empty
+// Reserve left without any LS token is not priced in, the pool moves it to the rounding dust on the next delegation
+pub fn ls_token_amount_for_xoxno<M: ManagedTypeApi>(
+    xoxno_amount: &BigUint<M>,
+    pool: &impl PoolState<M>,
+) -> PricingResult<M> {
+    if *xoxno_amount == 0 {
+        return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+    }
+
+    let ls_token_amount = if *pool.ls_token_supply() > 0 && *pool.virtual_xoxno_reserve() > 0 {
+        mul_div_floor(
+            xoxno_amount,
+            pool.ls_token_supply(),
+            pool.virtual_xoxno_reserve(),
+        )
+    } else {
+        xoxno_amount.clone()
+    };
+    if ls_token_amount == 0 {
+        return Err(ERROR_INSUFFICIENT_LIQUIDITY);
+    }
+
+    Ok(ls_token_amount)
+}
+
+// XOXNO released for an undelegated LS token amount
+pub fn xoxno_amount_for_ls_token<M: ManagedTypeApi>(
+    ls_token_amount: &BigUint<M>,
+    pool: &impl PoolState<M>,
+) -> PricingResult<M> {
+    if pool.ls_token_supply() < ls_token_amount {
+        return Err(ERROR_NOT_ENOUGH_LP);
+    }
+    if *ls_token_amount == 0 {
+        return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+    }
+
+    let xoxno_amount = mul_div_floor(
+        ls_token_amount,
+        pool.virtual_xoxno_reserve(),
+        pool.ls_token_supply(),
+    );
+    if xoxno_amount == 0 {
+        return Err(ERROR_INSUFFICIENT_LIQ_BURNED);
+    }
+
+    Ok(xoxno_amount)
+}
+
+// Whole XOXNO units of a delegation not needed to back the minted LS tokens, computed on the pool before the delegation
+// Redeeming only ever truncates less than one unit, so delegations are the only source of dust
+pub fn delegation_dust<M: ManagedTypeApi>(
+    xoxno_amount: &BigUint<M>,
+    ls_token_amount: &BigUint<M>,
+    pool: &impl PoolState<M>,
+) -> BigUint<M> {
+    if *pool.virtual_xoxno_reserve() == 0 || *pool.ls_token_supply() == 0 {
+        return BigUint::zero();
+    }
+
+    xoxno_amount
+        - &mul_div_ceil(
+            ls_token_amount,
+            pool.virtual_xoxno_reserve(),
+            pool.ls_token_supply(),
+        )
+}
+
+// XOXNO reserve over LS token supply, one to one while no LS token exists since that is what the next delegation mints at
+pub fn exchange_rate_fraction<M: ManagedTypeApi>(
+    pool: &impl PoolState<M>,
+) -> (BigUint<M>, BigUint<M>) {
+    if *pool.ls_token_supply() == 0 {
+        return (BigUint::from(1u32), BigUint::from(1u32));
+    }
+
+    (
+        pool.virtual_xoxno_reserve().clone(),
+        pool.ls_token_supply().clone(),
+    )
+}
+
+// XOXNO per LS token scaled by 10^decimals, rounded down
+pub fn exchange_rate_with_precision<M: ManagedTypeApi>(
+    pool: &impl PoolState<M>,
+    decimals: u32,
+) -> BigUint<M> {
+    let (reserve, supply) = exchange_rate_fraction(pool);
+    mul_div_floor(&reserve, &BigUint::from(10u32).pow(decimals), &supply)
+}
+
+// XOXNO per LS token, scaled by INITIAL_EXCHANGE_RATE
+pub fn exchange_rate<M: ManagedTypeApi>(pool: &impl PoolState<M>) -> BigUint<M> {
+    let (reserve, supply) = exchange_rate_fraction(pool);
+    mul_div_floor(&reserve, &BigUint::from(INITIAL_EXCHANGE_RATE), &supply)
+}
+
+// Reserve left without any LS token, e.g. rewards added to an empty pool, would otherwise go to the first delegator
+pub fn apply_delegation<M: ManagedTypeApi>(
+    xoxno_amount: &BigUint<M>,
+    pool: &mut impl PoolStateMut<M>,
+) -> Result<Delegation<M>, &'static [u8]> {
+    let ls_token_amount = ls_token_amount_for_xoxno(xoxno_amount, pool)?;
+    let orphaned_reserve = if *pool.ls_token_supply() == 0 {
+        core::mem::replace(pool.virtual_xoxno_reserve_mut(), BigUint::zero())
+    } else {
+        BigUint::zero()
+    };
+
+    // The dust is kept out of the reserve, so the LS token holders never get to redeem it
+    let rounding_dust = delegation_dust(xoxno_amount, &ls_token_amount, pool);
+    *pool.ls_token_supply_mut() += &ls_token_amount;
+    *pool.virtual_xoxno_reserve_mut() += &(xoxno_amount - &rounding_dust);
+
+    Ok(Delegation {
+        ls_token_amount,
+        rounding_dust,
+        orphaned_reserve,
+    })
+}
+
+pub fn remove_liquidity<M: ManagedTypeApi>(
+    ls_token_amount: &BigUint<M>,
+    pool: &mut impl PoolStateMut<M>,
+) -> PricingResult<M> {
+    let xoxno_amount = xoxno_amount_for_ls_token(ls_token_amount, pool)?;
+    *pool.ls_token_supply_mut() -= ls_token_amount;
+    *pool.virtual_xoxno_reserve_mut() -= &xoxno_amount;
+
+    Ok(xoxno_amount)
+}
+
+// The tier fee flows back to the pool and is distributed to the remaining LS token holders
+pub fn apply_undelegation<M: ManagedTypeApi>(
+    ls_token_amount: &BigUint<M>,
+    tier_fee: u64,
+    pool: &mut impl PoolStateMut<M>,
+) -> Result<Undelegation<M>, &'static [u8]> {
+    let xoxno_removed = remove_liquidity(ls_token_amount, pool)?;
+    let fee_amount = mul_div_ceil(
+        &xoxno_removed,
+        &BigUint::from(tier_fee),
+        &BigUint::from(MAX_PERCENTAGE),
+    );
+    *pool.virtual_xoxno_reserve_mut() += &fee_amount;
+    let xoxno_to_unstake = &xoxno_removed - &fee_amount;
+
+    Ok(Undelegation {
+        xoxno_removed,
+        fee_amount,
+        xoxno_to_unstake,
+    })
+}
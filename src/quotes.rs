@@ -0,0 +1,256 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::config::DEFAULT_UNBOND_TIER;
+use crate::contexts::readonly::ReadOnlyStorageCache;
+use crate::errors::*;
+use crate::pricing::{self, PoolSnapshot};
+
+// Why the quoted action would revert, the codes are part of the ABI so new ones are only ever appended
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuoteError {
+    None,
+    NotActive,
+    BadPaymentAmount,
+    DelegateAmountTooLow,
+    TotalCapExceeded,
+    AddressCapExceeded,
+    InsufficientLiquidity,
+    LsTokenNotIssued,
+    NotEnoughLsSupply,
+    UnknownUnbondTier,
+    UndelegateAmountTooLow,
+    UnbondLimitExceeded,
+    UnstakeTokenNotIssued,
+    UnknownPosition,
+    UnstakePeriodNotPassed,
+}
+
+impl QuoteError {
+    fn from_pricing_error(error: &[u8]) -> Self {
+        if error == ERROR_NOT_ENOUGH_LP {
+            QuoteError::NotEnoughLsSupply
+        } else {
+            QuoteError::InsufficientLiquidity
+        }
+    }
+}
+
+// Outcome of a user action as if it was sent now, the amounts are zero and the exchange rate the current one on error
+// The output is the LS tokens minted, the XOXNO unstaked or the XOXNO withdrawn, the fee the part of the input the user gives up
+// unbond_epoch is the epoch the XOXNO can be withdrawn at, zero for a delegation
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct Quote<M: ManagedTypeApi> {
+    pub output_amount: BigUint<M>,
+    pub fee_amount: BigUint<M>,
+    pub exchange_rate: BigUint<M>,
+    pub unbond_epoch: u64,
+    pub error: QuoteError,
+}
+
+// Dry runs of the user endpoints that never revert, so front-ends can render previews
+#[multiversx_sc::module]
+pub trait QuotesModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::liquidity_pool::LiquidityPoolModule
+    + crate::stats::StatsModule
+    + crate::reports::ReportsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // The fee is the rounding dust kept out of the reserve, the address cap is only checked for a given delegator
+    #[view(quoteDelegate)]
+    fn quote_delegate(
+        &self,
+        amount: BigUint,
+        delegator: OptionalValue<ManagedAddress>,
+    ) -> Quote<Self::Api> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        self.try_quote_delegate(&amount, delegator.into_option(), &storage_cache)
+            .unwrap_or_else(|error| self.failed_quote(error, &storage_cache))
+    }
+
+    // The fee is the unbond tier fee, positions paid out instantly unbond in the current epoch
+    #[view(quoteUnDelegate)]
+    fn quote_un_delegate(
+        &self,
+        ls_token_amount: BigUint,
+        tier_id: OptionalValue<u8>,
+    ) -> Quote<Self::Api> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        let tier_id = tier_id.into_option().unwrap_or(DEFAULT_UNBOND_TIER);
+        self.try_quote_un_delegate(&ls_token_amount, tier_id, &storage_cache)
+            .unwrap_or_else(|error| self.failed_quote(error, &storage_cache))
+    }
+
+    // Unstake token nonces with the quantity sent, which is ignored for per-position tokens
+    // The unbond epoch is the latest one of the positions
+    #[view(quoteWithdraw)]
+    fn quote_withdraw(
+        &self,
+        positions: MultiValueEncoded<MultiValue2<u64, BigUint>>,
+    ) -> Quote<Self::Api> {
+        let storage_cache = ReadOnlyStorageCache::new(self);
+        self.try_quote_withdraw(positions, &storage_cache)
+            .unwrap_or_else(|error| self.failed_quote(error, &storage_cache))
+    }
+
+    fn try_quote_delegate(
+        &self,
+        amount: &BigUint,
+        delegator: Option<ManagedAddress>,
+        storage_cache: &ReadOnlyStorageCache<Self>,
+    ) -> Result<Quote<Self::Api>, QuoteError> {
+        self.require_quote_active(storage_cache)?;
+        if !self.ls_token().get_token_state().is_set() {
+            return Err(QuoteError::LsTokenNotIssued);
+        }
+        if *amount == 0 {
+            return Err(QuoteError::BadPaymentAmount);
+        }
+        if amount < &self.min_delegate_amount().get() {
+            return Err(QuoteError::DelegateAmountTooLow);
+        }
+        if let Some(remaining) =
+            self.compute_remaining_total_capacity(storage_cache.virtual_xoxno_reserve())
+        {
+            if amount > &remaining {
+                return Err(QuoteError::TotalCapExceeded);
+            }
+        }
+        if let Some(remaining) =
+            delegator.and_then(|delegator| self.compute_remaining_address_capacity(&delegator))
+        {
+            if amount > &remaining {
+                return Err(QuoteError::AddressCapExceeded);
+            }
+        }
+
+        let mut pool = PoolSnapshot::from_pool(storage_cache);
+        let delegation =
+            pricing::apply_delegation(amount, &mut pool).map_err(QuoteError::from_pricing_error)?;
+
+        Ok(Quote {
+            output_amount: delegation.ls_token_amount,
+            fee_amount: delegation.rounding_dust,
+            exchange_rate: pricing::exchange_rate(&pool),
+            unbond_epoch: 0,
+            error: QuoteError::None,
+        })
+    }
+
+    fn try_quote_un_delegate(
+        &self,
+        ls_token_amount: &BigUint,
+        tier_id: u8,
+        storage_cache: &ReadOnlyStorageCache<Self>,
+    ) -> Result<Quote<Self::Api>, QuoteError> {
+        self.require_quote_active(storage_cache)?;
+        if !self.ls_token().get_token_state().is_set() {
+            return Err(QuoteError::LsTokenNotIssued);
+        }
+        if *ls_token_amount == 0 {
+            return Err(QuoteError::BadPaymentAmount);
+        }
+        let tier = self
+            .find_unbond_tier(tier_id)
+            .ok_or(QuoteError::UnknownUnbondTier)?;
+
+        let mut pool = PoolSnapshot::from_pool(storage_cache);
+        let undelegation = pricing::apply_undelegation(ls_token_amount, tier.fee, &mut pool)
+            .map_err(QuoteError::from_pricing_error)?;
+        if undelegation.xoxno_removed < self.min_undelegate_amount().get() {
+            return Err(QuoteError::UndelegateAmountTooLow);
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unbond_epoch =
+            if self.is_instant_withdraw(&undelegation.xoxno_to_unstake, current_epoch) {
+                current_epoch
+            } else {
+                self.find_unbond_epoch(
+                    &undelegation.xoxno_to_unstake,
+                    current_epoch + tier.unbond_epochs,
+                )
+                .ok_or(QuoteError::UnbondLimitExceeded)?
+            };
+
+        Ok(Quote {
+            output_amount: undelegation.xoxno_to_unstake,
+            fee_amount: undelegation.fee_amount,
+            exchange_rate: pricing::exchange_rate(&pool),
+            unbond_epoch,
+            error: QuoteError::None,
+        })
+    }
+
+    fn try_quote_withdraw(
+        &self,
+        positions: MultiValueEncoded<MultiValue2<u64, BigUint>>,
+        storage_cache: &ReadOnlyStorageCache<Self>,
+    ) -> Result<Quote<Self::Api>, QuoteError> {
+        self.require_quote_active(storage_cache)?;
+        if !self.unstake_token().get_token_state().is_set() {
+            return Err(QuoteError::UnstakeTokenNotIssued);
+        }
+        if positions.is_empty() {
+            return Err(QuoteError::BadPaymentAmount);
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut total_unstaked = BigUint::zero();
+        let mut unbond_epoch = 0;
+        for position in positions.into_iter() {
+            let (nonce, amount) = position.into_tuple();
+            if amount == 0 {
+                return Err(QuoteError::BadPaymentAmount);
+            }
+
+            let attributes = self
+                .try_get_unstake_position(nonce, &amount)
+                .ok_or(QuoteError::UnknownPosition)?;
+            if current_epoch < attributes.unbond_epoch {
+                return Err(QuoteError::UnstakePeriodNotPassed);
+            }
+
+            total_unstaked += self.compute_unstake_value(&attributes);
+            unbond_epoch = unbond_epoch.max(attributes.unbond_epoch);
+        }
+
+        // Withdrawals only release unstaked XOXNO, the reserve and the supply stay the same
+        Ok(Quote {
+            output_amount: total_unstaked,
+            fee_amount: BigUint::zero(),
+            exchange_rate: self.compute_exchange_rate(storage_cache),
+            unbond_epoch,
+            error: QuoteError::None,
+        })
+    }
+
+    fn require_quote_active(
+        &self,
+        storage_cache: &ReadOnlyStorageCache<Self>,
+    ) -> Result<(), QuoteError> {
+        if self.is_state_active(storage_cache.contract_state()) {
+            Ok(())
+        } else {
+            Err(QuoteError::NotActive)
+        }
+    }
+
+    fn failed_quote(
+        &self,
+        error: QuoteError,
+        storage_cache: &ReadOnlyStorageCache<Self>,
+    ) -> Quote<Self::Api> {
+        Quote {
+            output_amount: BigUint::zero(),
+            fee_amount: BigUint::zero(),
+            exchange_rate: self.compute_exchange_rate(storage_cache),
+            unbond_epoch: 0,
+            error,
+        }
+    }
+}
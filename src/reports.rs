@@ -0,0 +1,102 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::contexts::base::StorageCache;
+use crate::pricing;
+
+// Number of epoch reports kept, the oldest report is overwritten once the buffer is full
+pub const EPOCH_REPORTS_CAPACITY: u64 = 365;
+
+// Amounts are in XOXNO, the exchange rates are scaled by INITIAL_EXCHANGE_RATE
+// Epochs without any operation have no report, the rate stays at the end rate of the previous one
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct EpochReport<M: ManagedTypeApi> {
+    pub epoch: u64,
+    pub delegated: BigUint<M>,
+    pub undelegated: BigUint<M>, // Net of the unbond tier fee
+    pub withdrawn: BigUint<M>, // Including instant payouts and emergency redemptions, net of the early withdraw penalty
+    pub rewards: BigUint<M>,
+    pub start_exchange_rate: BigUint<M>, // End rate of the previous report, the rate after the first operation for the very first one
+    pub end_exchange_rate: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait ReportsModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // Reports by sequence index, oldest first, indexes already overwritten are skipped
+    #[view(getEpochReports)]
+    fn get_epoch_reports(
+        &self,
+        from_index: u64,
+        max_reports: u64,
+    ) -> MultiValueEncoded<EpochReport<Self::Api>> {
+        let report_count = self.epoch_report_count().get();
+        let first_index = from_index.max(report_count.saturating_sub(EPOCH_REPORTS_CAPACITY));
+        let end_index = report_count.min(first_index.saturating_add(max_reports));
+
+        let mut reports = MultiValueEncoded::new();
+        for index in first_index..end_index {
+            reports.push(self.epoch_reports(index % EPOCH_REPORTS_CAPACITY).get());
+        }
+
+        reports
+    }
+
+    // Called after the operation was applied to the storage cache
+    fn update_epoch_report(
+        &self,
+        epoch: u64,
+        storage_cache: &StorageCache<Self>,
+        update: impl FnOnce(&mut EpochReport<Self::Api>),
+    ) {
+        let exchange_rate = pricing::exchange_rate(storage_cache);
+        let report_count = self.epoch_report_count().get();
+        let latest_report = if report_count > 0 {
+            Some(
+                self.epoch_reports((report_count - 1) % EPOCH_REPORTS_CAPACITY)
+                    .get(),
+            )
+        } else {
+            None
+        };
+
+        let (index, mut report) = match latest_report {
+            Some(report) if report.epoch == epoch => (report_count - 1, report),
+            latest_report => {
+                self.epoch_report_count().set(report_count + 1);
+                let start_exchange_rate = latest_report
+                    .map(|report| report.end_exchange_rate)
+                    .unwrap_or_else(|| exchange_rate.clone());
+                (
+                    report_count,
+                    EpochReport {
+                        epoch,
+                        delegated: BigUint::zero(),
+                        undelegated: BigUint::zero(),
+                        withdrawn: BigUint::zero(),
+                        rewards: BigUint::zero(),
+                        start_exchange_rate,
+                        end_exchange_rate: BigUint::zero(),
+                    },
+                )
+            }
+        };
+
+        update(&mut report);
+        report.end_exchange_rate = exchange_rate;
+        self.epoch_reports(index % EPOCH_REPORTS_CAPACITY)
+            .set(report);
+    }
+
+    #[storage_mapper("epochReports")]
+    fn epoch_reports(&self, slot: u64) -> SingleValueMapper<EpochReport<Self::Api>>;
+
+    // Total number of reports ever written, including the overwritten ones
+    #[view(getEpochReportCount)]
+    #[storage_mapper("epochReportCount")]
+    fn epoch_report_count(&self) -> SingleValueMapper<u64>;
+}
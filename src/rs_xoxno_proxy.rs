@@ -90,18 +90,34 @@ where
     >(
         self,
         delegator: Arg0,
-    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+    ) -> TxTypedCall<Env, From, To, (), Gas, EsdtTokenPayment<Env::Api>> {
         self.wrapped_tx
             .raw_call("delegate")
             .argument(&delegator)
             .original_result()
     }
 
-    pub fn un_delegate(
+    pub fn delegate_split<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, u64>>>,
+    >(
+        self,
+        recipients: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, MultiValueEncoded<Env::Api, EsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .raw_call("delegateSplit")
+            .argument(&recipients)
+            .original_result()
+    }
+
+    pub fn un_delegate<
+        Arg0: ProxyArg<OptionalValue<u8>>,
+    >(
         self,
+        tier_id: Arg0,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
         self.wrapped_tx
             .raw_call("unDelegate")
+            .argument(&tier_id)
             .original_result()
     }
 
@@ -113,6 +129,14 @@ where
             .original_result()
     }
 
+    pub fn early_withdraw(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, EsdtTokenPayment<Env::Api>> {
+        self.wrapped_tx
+            .raw_call("earlyWithdraw")
+            .original_result()
+    }
+
     pub fn add_rewards(
         self,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
@@ -121,6 +145,31 @@ where
             .original_result()
     }
 
+    pub fn apply_loss<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        loss_amount: Arg0,
+        include_pending: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("applyLoss")
+            .argument(&loss_amount)
+            .argument(&include_pending)
+            .original_result()
+    }
+
+    pub fn sweep_rounding_dust(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("sweepRoundingDust")
+            .original_result()
+    }
+
     pub fn get_ls_value_for_position<
         Arg0: ProxyArg<BigUint<Env::Api>>,
     >(
@@ -147,6 +196,96 @@ where
             .original_result()
     }
 
+    pub fn get_early_withdraw_penalty<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
+        self,
+        nonce: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEarlyWithdrawPenalty")
+            .argument(&nonce)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn get_unstake_token_metadata<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        nonce: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakeTokenMetadata")
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn get_unstake_position_info<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<u64, BigUint<Env::Api>>>>,
+    >(
+        self,
+        positions: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, UnstakePositionInfo<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakePositionInfo")
+            .argument(&positions)
+            .original_result()
+    }
+
+    pub fn get_remaining_total_capacity(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRemainingTotalCapacity")
+            .original_result()
+    }
+
+    pub fn get_remaining_address_capacity<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRemainingAddressCapacity")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_remaining_unbond_capacity<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRemainingUnbondCapacity")
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn get_unbond_queue_depth<
+        Arg0: ProxyArg<OptionalValue<u8>>,
+    >(
+        self,
+        tier_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnbondQueueDepth")
+            .argument(&tier_id)
+            .original_result()
+    }
+
     pub fn get_exchange_rate(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
@@ -156,6 +295,37 @@ where
             .original_result()
     }
 
+    pub fn get_contract_summary(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ContractSummary<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getContractSummary")
+            .original_result()
+    }
+
+    pub fn get_exchange_rate_fraction(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<BigUint<Env::Api>, BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getExchangeRateFraction")
+            .original_result()
+    }
+
+    pub fn get_exchange_rate_with_precision<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        decimals: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getExchangeRateWithPrecision")
+            .argument(&decimals)
+            .original_result()
+    }
+
     pub fn register_ls_token<
         Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
         Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
@@ -178,17 +348,20 @@ where
         Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
         Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
         Arg2: ProxyArg<usize>,
+        Arg3: ProxyArg<OptionalValue<UnstakeTokenMode>>,
     >(
         self,
         token_display_name: Arg0,
         token_ticker: Arg1,
         num_decimals: Arg2,
+        mode: Arg3,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
         self.wrapped_tx
             .raw_call("registerUnstakeToken")
             .argument(&token_display_name)
             .argument(&token_ticker)
             .argument(&num_decimals)
+            .argument(&mode)
             .original_result()
     }
 
@@ -210,6 +383,258 @@ where
             .original_result()
     }
 
+    pub fn set_early_withdraw_max_penalty<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        max_penalty: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setEarlyWithdrawMaxPenalty")
+            .argument(&max_penalty)
+            .original_result()
+    }
+
+    pub fn set_unbond_tier<
+        Arg0: ProxyArg<u8>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        tier_id: Arg0,
+        unbond_epochs: Arg1,
+        fee: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setUnbondTier")
+            .argument(&tier_id)
+            .argument(&unbond_epochs)
+            .argument(&fee)
+            .original_result()
+    }
+
+    pub fn remove_unbond_tier<
+        Arg0: ProxyArg<u8>,
+    >(
+        self,
+        tier_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeUnbondTier")
+            .argument(&tier_id)
+            .original_result()
+    }
+
+    pub fn set_max_unbond_per_epoch<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        max_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxUnbondPerEpoch")
+            .argument(&max_amount)
+            .original_result()
+    }
+
+    pub fn set_max_total_reserve<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        max_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxTotalReserve")
+            .argument(&max_amount)
+            .original_result()
+    }
+
+    pub fn set_max_delegation_per_address<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        max_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxDelegationPerAddress")
+            .argument(&max_amount)
+            .original_result()
+    }
+
+    pub fn set_min_delegate_amount<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        min_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMinDelegateAmount")
+            .argument(&min_amount)
+            .original_result()
+    }
+
+    pub fn set_min_undelegate_amount<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        min_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMinUnDelegateAmount")
+            .argument(&min_amount)
+            .original_result()
+    }
+
+    pub fn set_instant_withdraw_threshold<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        threshold: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setInstantWithdrawThreshold")
+            .argument(&threshold)
+            .original_result()
+    }
+
+    pub fn set_max_loss_per_call<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        max_loss: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxLossPerCall")
+            .argument(&max_loss)
+            .original_result()
+    }
+
+    pub fn set_treasury<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        treasury: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTreasury")
+            .argument(&treasury)
+            .original_result()
+    }
+
+    pub fn set_unstake_token_uris<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        uris: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setUnstakeTokenUris")
+            .argument(&uris)
+            .original_result()
+    }
+
+    pub fn set_unstake_token_name_template<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        template: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setUnstakeTokenNameTemplate")
+            .argument(&template)
+            .original_result()
+    }
+
+    pub fn get_unstake_token_uris(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, ManagedBuffer<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakeTokenUris")
+            .original_result()
+    }
+
+    pub fn get_unstake_token_name_template(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakeTokenNameTemplate")
+            .original_result()
+    }
+
+    pub fn get_epoch_unstake_nonce<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u8>,
+    >(
+        self,
+        unbond_epoch: Arg0,
+        unstake_epoch: Arg1,
+        tier_id: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEpochUnstakeNonce")
+            .argument(&unbond_epoch)
+            .argument(&unstake_epoch)
+            .argument(&tier_id)
+            .original_result()
+    }
+
+    pub fn get_epoch_unstake_nonces<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        unbond_epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEpochUnstakeNonces")
+            .argument(&unbond_epoch)
+            .original_result()
+    }
+
+    pub fn get_max_loss_per_call(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxLossPerCall")
+            .original_result()
+    }
+
+    pub fn get_unstake_value_index(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakeValueIndex")
+            .original_result()
+    }
+
+    pub fn get_unbond_tiers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue3<u8, u64, u64>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnbondTiers")
+            .original_result()
+    }
+
     pub fn state(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, State> {
@@ -272,13 +697,494 @@ where
             .raw_call("getUnstakeTokenSupply")
             .original_result()
     }
-}
 
-#[type_abi]
-#[derive(TopEncode, TopDecode)]
+    pub fn outstanding_unstake_amount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutstandingUnstakeAmount")
+            .original_result()
+    }
+
+    pub fn early_withdraw_max_penalty(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEarlyWithdrawMaxPenalty")
+            .original_result()
+    }
+
+    pub fn max_unbond_per_epoch(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxUnbondPerEpoch")
+            .original_result()
+    }
+
+    pub fn scheduled_unbond<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getScheduledUnbond")
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn max_total_reserve(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxTotalReserve")
+            .original_result()
+    }
+
+    pub fn max_delegation_per_address(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxDelegationPerAddress")
+            .original_result()
+    }
+
+    pub fn delegated_amount<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDelegatedAmount")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn min_delegate_amount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinDelegateAmount")
+            .original_result()
+    }
+
+    pub fn min_undelegate_amount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinUnDelegateAmount")
+            .original_result()
+    }
+
+    pub fn instant_withdraw_threshold(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getInstantWithdrawThreshold")
+            .original_result()
+    }
+
+    pub fn unstake_token_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, UnstakeTokenMode> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnstakeTokenMode")
+            .original_result()
+    }
+
+    pub fn treasury(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTreasury")
+            .original_result()
+    }
+
+    pub fn rounding_dust(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoundingDust")
+            .original_result()
+    }
+
+    pub fn get_protocol_stats(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ProtocolStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProtocolStats")
+            .original_result()
+    }
+
+    pub fn get_epoch_reports<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        from_index: Arg0,
+        max_reports: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EpochReport<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEpochReports")
+            .argument(&from_index)
+            .argument(&max_reports)
+            .original_result()
+    }
+
+    pub fn epoch_report_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEpochReportCount")
+            .original_result()
+    }
+
+    pub fn migrate_storage(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OperationCompletionStatus> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateStorage")
+            .original_result()
+    }
+
+    pub fn storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    pub fn storage_migration_cursor(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageMigrationCursor")
+            .original_result()
+    }
+
+    pub fn add_airdrop_entries<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, BigUint<Env::Api>>>>,
+    >(
+        self,
+        entries: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("addAirdropEntries")
+            .argument(&entries)
+            .original_result()
+    }
+
+    pub fn process_airdrop(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OperationCompletionStatus> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("processAirdrop")
+            .original_result()
+    }
+
+    pub fn cancel_airdrop(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OperationCompletionStatus> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelAirdrop")
+            .original_result()
+    }
+
+    pub fn get_airdrop_progress(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<usize, BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAirdropProgress")
+            .original_result()
+    }
+
+    pub fn airdrop_pending_amount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAirdropPendingAmount")
+            .original_result()
+    }
+
+    pub fn enable_emergency_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("enableEmergencyMode")
+            .original_result()
+    }
+
+    pub fn emergency_redeem(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, EsdtTokenPayment<Env::Api>> {
+        self.wrapped_tx
+            .raw_call("emergencyRedeem")
+            .original_result()
+    }
+
+    pub fn get_contract_owner(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getContractOwner")
+            .original_result()
+    }
+
+    pub fn pending_owner(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingOwner")
+            .original_result()
+    }
+
+    pub fn propose_owner<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        new_owner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("proposeOwner")
+            .argument(&new_owner)
+            .original_result()
+    }
+
+    pub fn accept_ownership(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("acceptOwnership")
+            .original_result()
+    }
+
+    pub fn cancel_ownership_proposal(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelOwnershipProposal")
+            .original_result()
+    }
+
+    pub fn set_ls_token_id<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLsTokenId")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn set_unstake_token_id<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<UnstakeTokenMode>>,
+    >(
+        self,
+        token_id: Arg0,
+        mode: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setUnstakeTokenId")
+            .argument(&token_id)
+            .argument(&mode)
+            .original_result()
+    }
+
+    pub fn request_ls_token_roles(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("requestLsTokenRoles")
+            .original_result()
+    }
+
+    pub fn request_unstake_token_roles(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("requestUnstakeTokenRoles")
+            .original_result()
+    }
+
+    pub fn migrate_ls_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, EsdtTokenPayment<Env::Api>> {
+        self.wrapped_tx
+            .raw_call("migrateLsToken")
+            .original_result()
+    }
+
+    pub fn get_missing_token_roles(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<TokenIdentifier<Env::Api>, ManagedBuffer<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMissingTokenRoles")
+            .original_result()
+    }
+
+    pub fn legacy_ls_token_ids(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLegacyLsTokenIds")
+            .original_result()
+    }
+
+    pub fn quote_delegate<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        amount: Arg0,
+        delegator: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Quote<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("quoteDelegate")
+            .argument(&amount)
+            .argument(&delegator)
+            .original_result()
+    }
+
+    pub fn quote_un_delegate<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<u8>>,
+    >(
+        self,
+        ls_token_amount: Arg0,
+        tier_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Quote<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("quoteUnDelegate")
+            .argument(&ls_token_amount)
+            .argument(&tier_id)
+            .original_result()
+    }
+
+    pub fn quote_withdraw<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<u64, BigUint<Env::Api>>>>,
+    >(
+        self,
+        positions: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Quote<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("quoteWithdraw")
+            .argument(&positions)
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct UnstakePositionInfo<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub nonce: u64,
+    pub original_amount: BigUint<Api>,
+    pub share_amount: BigUint<Api>,
+    pub current_value: BigUint<Api>,
+    pub haircut_amount: BigUint<Api>,
+    pub haircut: u64,
+    pub unstake_epoch: u64,
+    pub unbond_epoch: u64,
+    pub epochs_remaining: u64,
+    pub tier_id: u8,
+    pub withdrawable: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct ContractSummary<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub state: State,
+    pub main_token_id: TokenIdentifier<Api>,
+    pub ls_token_id: Option<TokenIdentifier<Api>>,
+    pub unstake_token_id: Option<TokenIdentifier<Api>>,
+    pub unstake_token_mode: UnstakeTokenMode,
+    pub ls_token_supply: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub unstake_token_supply: BigUint<Api>,
+    pub rounding_dust: BigUint<Api>,
+    pub exchange_rate: BigUint<Api>,
+    pub unbond_period: u64,
+    pub current_epoch: u64,
+    pub contract_balance: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum State {
     Inactive,
     Active,
+    Emergency,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnstakeTokenMode {
+    PerPosition,
+    PerEpoch,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct ProtocolStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub total_delegated: BigUint<Api>,
+    pub total_undelegated: BigUint<Api>,
+    pub total_withdrawn: BigUint<Api>,
+    pub total_rewards: BigUint<Api>,
+    pub delegate_count: u64,
+    pub undelegate_count: u64,
+    pub unique_beneficiaries: u64,
 }
 
 #[type_abi]
@@ -291,6 +1197,7 @@ where
     pub ls_token_id: TokenIdentifier<Api>,
     pub ls_token_amount: BigUint<Api>,
     pub ls_token_supply: BigUint<Api>,
+    pub original_amount: BigUint<Api>,
     pub virtual_xoxno_reserve: BigUint<Api>,
     pub block: u64,
     pub epoch: u64,
@@ -306,9 +1213,27 @@ where
     pub caller: ManagedAddress<Api>,
     pub ls_token_id: TokenIdentifier<Api>,
     pub ls_token_amount: BigUint<Api>,
-    pub unstake_token_id: TokenIdentifier<Api>,
-    pub unstake_token_amount: BigUint<Api>,
     pub ls_token_supply: BigUint<Api>,
+    pub original_amount: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub unbound_nft: EsdtTokenPayment<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct InstantWithdrawEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub caller: ManagedAddress<Api>,
+    pub ls_token_id: TokenIdentifier<Api>,
+    pub ls_token_amount: BigUint<Api>,
+    pub ls_token_supply: BigUint<Api>,
+    pub main_token_id: TokenIdentifier<Api>,
+    pub withdrawn_amount: BigUint<Api>,
     pub virtual_xoxno_reserve: BigUint<Api>,
     pub block: u64,
     pub epoch: u64,
@@ -330,3 +1255,174 @@ where
     pub epoch: u64,
     pub timestamp: u64,
 }
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct EarlyWithdrawEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub caller: ManagedAddress<Api>,
+    pub main_token_id: TokenIdentifier<Api>,
+    pub withdrawn_amount: BigUint<Api>,
+    pub penalty_amount: BigUint<Api>,
+    pub ls_token_supply: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct ApplyLossEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub caller: ManagedAddress<Api>,
+    pub ls_token_id: TokenIdentifier<Api>,
+    pub ls_token_supply: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub reserve_loss: BigUint<Api>,
+    pub pending_loss: BigUint<Api>,
+    pub exchange_rate: BigUint<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct EmergencyModeEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub caller: ManagedAddress<Api>,
+    pub ls_token_id: TokenIdentifier<Api>,
+    pub ls_token_supply: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub total_unstaked_xoxno: BigUint<Api>,
+    pub redeemable_balance: BigUint<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SweepRoundingDustEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub caller: ManagedAddress<Api>,
+    pub treasury: ManagedAddress<Api>,
+    pub main_token_id: TokenIdentifier<Api>,
+    pub dust_amount: BigUint<Api>,
+    pub ls_token_supply: BigUint<Api>,
+    pub virtual_xoxno_reserve: BigUint<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipProposedEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub owner: ManagedAddress<Api>,
+    pub pending_owner: ManagedAddress<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipTransferredEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub previous_owner: ManagedAddress<Api>,
+    pub new_owner: ManagedAddress<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct OwnershipProposalCancelledEvent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub owner: ManagedAddress<Api>,
+    pub pending_owner: ManagedAddress<Api>,
+    pub block: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct EpochReport<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub epoch: u64,
+    pub delegated: BigUint<Api>,
+    pub undelegated: BigUint<Api>,
+    pub withdrawn: BigUint<Api>,
+    pub rewards: BigUint<Api>,
+    pub start_exchange_rate: BigUint<Api>,
+    pub end_exchange_rate: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct Quote<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub output_amount: BigUint<Api>,
+    pub fee_amount: BigUint<Api>,
+    pub exchange_rate: BigUint<Api>,
+    pub unbond_epoch: u64,
+    pub error: QuoteError,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuoteError {
+    None,
+    NotActive,
+    BadPaymentAmount,
+    DelegateAmountTooLow,
+    TotalCapExceeded,
+    AddressCapExceeded,
+    InsufficientLiquidity,
+    LsTokenNotIssued,
+    NotEnoughLsSupply,
+    UnknownUnbondTier,
+    UndelegateAmountTooLow,
+    UnbondLimitExceeded,
+    UnstakeTokenNotIssued,
+    UnknownPosition,
+    UnstakePeriodNotPassed,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode)]
+pub struct UnstakeTokenAttributes<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub version: u8,
+    pub original_amount: BigUint<Api>,
+    pub share_amount: BigUint<Api>,
+    pub unstake_epoch: u64,
+    pub unbond_epoch: u64,
+    pub tier_id: u8,
+    pub unstake_value_index: BigUint<Api>,
+}
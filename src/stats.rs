@@ -0,0 +1,91 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+// Totals since the counters were introduced, amounts are in XOXNO
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Eq, Debug)]
+pub struct ProtocolStats<M: ManagedTypeApi> {
+    pub total_delegated: BigUint<M>, // Including the processed airdrop entries
+    pub total_undelegated: BigUint<M>, // Unstaked or paid out instantly, net of the unbond tier fee
+    pub total_withdrawn: BigUint<M>, // Paid out for unstake tokens, instantly or by emergency redemptions, net of the early withdraw penalty
+    pub total_rewards: BigUint<M>,
+    pub delegate_count: u64, // Every airdrop entry counts as one delegation
+    pub undelegate_count: u64,
+    pub unique_beneficiaries: u64, // Addresses that received LS tokens through a delegation
+}
+
+#[multiversx_sc::module]
+pub trait StatsModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[view(getProtocolStats)]
+    fn get_protocol_stats(&self) -> ProtocolStats<Self::Api> {
+        ProtocolStats {
+            total_delegated: self.total_delegated().get(),
+            total_undelegated: self.total_undelegated().get(),
+            total_withdrawn: self.total_withdrawn().get(),
+            total_rewards: self.total_rewards().get(),
+            delegate_count: self.delegate_count().get(),
+            undelegate_count: self.undelegate_count().get(),
+            unique_beneficiaries: self.unique_beneficiaries().get(),
+        }
+    }
+
+    fn record_delegation(&self, amount: &BigUint) {
+        self.total_delegated().update(|total| *total += amount);
+        self.delegate_count().update(|count| *count += 1);
+    }
+
+    fn record_undelegation(&self, amount: &BigUint) {
+        self.total_undelegated().update(|total| *total += amount);
+        self.undelegate_count().update(|count| *count += 1);
+    }
+
+    fn record_withdrawal(&self, amount: &BigUint) {
+        self.total_withdrawn().update(|total| *total += amount);
+    }
+
+    fn record_rewards(&self, amount: &BigUint) {
+        self.total_rewards().update(|total| *total += amount);
+    }
+
+    // Called before the delegated amount is updated, an address that already has one was counted before the set existed
+    fn record_beneficiary(&self, beneficiary: &ManagedAddress) {
+        let beneficiaries = self.beneficiaries();
+        if beneficiaries.contains(beneficiary) {
+            return;
+        }
+
+        beneficiaries.add(beneficiary);
+        if self.delegated_amount(beneficiary).is_empty() {
+            self.unique_beneficiaries().update(|count| *count += 1);
+        }
+    }
+
+    #[storage_mapper("totalDelegated")]
+    fn total_delegated(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalUndelegated")]
+    fn total_undelegated(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalWithdrawn")]
+    fn total_withdrawn(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalRewards")]
+    fn total_rewards(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("delegateCount")]
+    fn delegate_count(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("undelegateCount")]
+    fn undelegate_count(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("uniqueBeneficiaries")]
+    fn unique_beneficiaries(&self) -> SingleValueMapper<u64>;
+
+    // Every address counted in the unique beneficiaries, a delegation may leave its delegated amount empty
+    #[storage_mapper("beneficiaries")]
+    fn beneficiaries(&self) -> WhitelistMapper<ManagedAddress>;
+}
@@ -0,0 +1,172 @@
+multiversx_sc::imports!();
+
+use crate::config::UnstakeTokenMode;
+use crate::errors::*;
+use crate::liquidity_pool::State;
+
+pub const LS_TOKEN_ROLES: &[EsdtLocalRole] = &[EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+pub const PER_POSITION_UNSTAKE_TOKEN_ROLES: &[EsdtLocalRole] =
+    &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn];
+pub const PER_EPOCH_UNSTAKE_TOKEN_ROLES: &[EsdtLocalRole] = &[
+    EsdtLocalRole::NftCreate,
+    EsdtLocalRole::NftBurn,
+    EsdtLocalRole::NftAddQuantity,
+];
+
+// Recovery paths for tokens issued with a bad ticker or that lost their local roles
+#[multiversx_sc::module]
+pub trait TokensModule:
+    crate::config::ConfigModule
+    + crate::ownership::OwnershipModule
+    + crate::liquidity_pool::LiquidityPoolModule
+    + crate::stats::StatsModule
+    + crate::reports::ReportsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    // The previous LS token stays swappable 1:1 through migrateLsToken
+    #[endpoint(setLsTokenId)]
+    fn set_ls_token_id(&self, token_id: TokenIdentifier) {
+        self.require_owner();
+        require!(token_id.is_valid_esdt_identifier(), ERROR_INVALID_TOKEN_ID);
+
+        // A pending issue left behind by a failed callback is simply discarded
+        if let TokenMapperState::Token(previous_token_id) = self.ls_token().get_token_state() {
+            if previous_token_id != token_id {
+                self.legacy_ls_token_ids().insert(previous_token_id);
+            }
+        }
+        self.legacy_ls_token_ids().swap_remove(&token_id);
+        self.ls_token_id_slot().clear();
+        self.ls_token().set_token_id(token_id);
+    }
+
+    // Only possible while no unstake token is outstanding, the old tokens could not be withdrawn otherwise
+    // The token type cannot be read on chain, so a per-epoch token must already grant the NftAddQuantity role,
+    // which only semi-fungible tokens can have, and a per-position one must not
+    #[endpoint(setUnstakeTokenId)]
+    fn set_unstake_token_id(
+        &self,
+        token_id: TokenIdentifier,
+        mode: OptionalValue<UnstakeTokenMode>,
+    ) {
+        self.require_owner();
+        require!(token_id.is_valid_esdt_identifier(), ERROR_INVALID_TOKEN_ID);
+        require!(
+            self.outstanding_unstake_amount().get() == 0,
+            ERROR_UNSTAKE_TOKENS_OUTSTANDING
+        );
+
+        let mode = mode.into_option().unwrap_or(UnstakeTokenMode::PerPosition);
+        let is_semi_fungible = self
+            .blockchain()
+            .get_esdt_local_roles(&token_id)
+            .has_role(&EsdtLocalRole::NftAddQuantity);
+        require!(
+            is_semi_fungible == (mode == UnstakeTokenMode::PerEpoch),
+            ERROR_UNSTAKE_TOKEN_TYPE_MISMATCH
+        );
+
+        self.unstake_token_mode().set(mode);
+        self.unstake_token_id_slot().clear();
+        self.unstake_token().set_token_id(token_id);
+    }
+
+    #[endpoint(requestLsTokenRoles)]
+    fn request_ls_token_roles(&self) {
+        self.require_owner();
+        self.ls_token().set_local_roles(LS_TOKEN_ROLES, None);
+    }
+
+    #[endpoint(requestUnstakeTokenRoles)]
+    fn request_unstake_token_roles(&self) {
+        self.require_owner();
+        self.unstake_token()
+            .set_local_roles(self.required_unstake_token_roles(), None);
+    }
+
+    // Swaps LS tokens of a previous LS token identifier for the current one 1:1
+    // The old tokens stay in the contract, since its roles on the old token may be gone, and the LS supply already counts them
+    // Paused with the other user endpoints while inactive, but still open in emergency mode so old tokens can be redeemed
+    #[payable("*")]
+    #[endpoint(migrateLsToken)]
+    fn migrate_ls_token(&self) -> EsdtTokenPayment {
+        let payments = self.call_value().all_esdt_transfers();
+        require!(self.state().get() != State::Inactive, ERROR_NOT_ACTIVE);
+        require!(!payments.is_empty(), ERROR_BAD_PAYMENT_AMOUNT);
+
+        let mut total_amount = BigUint::zero();
+        for payment in payments.iter() {
+            require!(
+                payment.token_nonce == 0
+                    && self
+                        .legacy_ls_token_ids()
+                        .contains(&payment.token_identifier),
+                ERROR_BAD_PAYMENT_TOKEN
+            );
+            total_amount += &payment.amount;
+        }
+        require!(total_amount > 0, ERROR_BAD_PAYMENT_AMOUNT);
+
+        let user_payment = self.mint_ls_token(total_amount);
+        self.tx().to(ToCaller).payment(&user_payment).transfer();
+
+        user_payment
+    }
+
+    // Token and role name of every local role the contract is missing, empty when all roles are set
+    #[view(getMissingTokenRoles)]
+    fn get_missing_token_roles(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<TokenIdentifier, ManagedBuffer>> {
+        let mut missing_roles = MultiValueEncoded::new();
+        if self.ls_token().get_token_state().is_set() {
+            self.push_missing_roles(
+                &self.ls_token().get_token_id(),
+                LS_TOKEN_ROLES,
+                &mut missing_roles,
+            );
+        }
+        if self.unstake_token().get_token_state().is_set() {
+            self.push_missing_roles(
+                &self.unstake_token().get_token_id(),
+                self.required_unstake_token_roles(),
+                &mut missing_roles,
+            );
+        }
+
+        missing_roles
+    }
+
+    fn push_missing_roles(
+        &self,
+        token_id: &TokenIdentifier,
+        required_roles: &[EsdtLocalRole],
+        missing_roles: &mut MultiValueEncoded<MultiValue2<TokenIdentifier, ManagedBuffer>>,
+    ) {
+        let roles = self.blockchain().get_esdt_local_roles(token_id);
+        for role in required_roles {
+            if !roles.has_role(role) {
+                missing_roles
+                    .push((token_id.clone(), ManagedBuffer::from(role.as_role_name())).into());
+            }
+        }
+    }
+
+    fn required_unstake_token_roles(&self) -> &'static [EsdtLocalRole] {
+        match self.unstake_token_mode().get() {
+            UnstakeTokenMode::PerPosition => PER_POSITION_UNSTAKE_TOKEN_ROLES,
+            UnstakeTokenMode::PerEpoch => PER_EPOCH_UNSTAKE_TOKEN_ROLES,
+        }
+    }
+
+    #[view(getLegacyLsTokenIds)]
+    #[storage_mapper("legacyLsTokenIds")]
+    fn legacy_ls_token_ids(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    // Same keys as the token mappers, used to replace a token identifier that was already set
+    #[storage_mapper("lsTokenId")]
+    fn ls_token_id_slot(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("unstakeTokenId")]
+    fn unstake_token_id_slot(&self) -> SingleValueMapper<TokenIdentifier>;
+}
@@ -1,7 +1,22 @@
 mod test_helpers;
-use multiversx_sc::types::{EgldOrEsdtTokenIdentifier, TestAddress};
-use multiversx_sc_scenario::{imports::SetStateStep, ExpectError, ScenarioTxRun};
-use rs_liquid_xoxno::rs_xoxno_proxy::{self, State};
+use multiversx_sc::codec::{
+    multi_types::{MultiValue2, OptionalValue},
+    TopDecode, TopEncode,
+};
+use multiversx_sc::types::{BigUint, ManagedBuffer};
+use multiversx_sc::types::{
+    EgldOrEsdtTokenIdentifier, EsdtLocalRole, MultiValueEncoded, TestAddress, TestEsdtTransfer,
+    TestTokenIdentifier,
+};
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::{imports::SetStateStep, ExpectError, ExpectValue, ScenarioTxRun};
+use rs_liquid_xoxno::config::{
+    UnstakeTokenAttributes, UnstakeTokenAttributesLayout, INITIAL_EXCHANGE_RATE,
+    UNSTAKE_TOKEN_ATTRIBUTES_VERSION, UNVERSIONED_ATTRIBUTES_TAG,
+};
+use rs_liquid_xoxno::migration::CURRENT_STORAGE_VERSION;
+use rs_liquid_xoxno::offchain::OffchainPool;
+use rs_liquid_xoxno::rs_xoxno_proxy::{self, QuoteError, State, UnstakeTokenMode};
 use test_helpers::*;
 
 #[test]
@@ -311,7 +326,7 @@ fn test_remove_excess_liquidity() {
         .from(DELEGATOR_ADDRESS)
         .to(RS_LIQUIDXOXNO_ADDRESS)
         .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
-        .un_delegate()
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::<u8>::None)
         .egld_or_single_esdt(
             &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
             0u64,
@@ -485,38 +500,2028 @@ fn test_add_rewards_and_observe_changes() {
     let additional_amount = 1_000_000u64;
     add_liquidity(&mut world, additional_amount);
 
-    // Calculate expected LP amount
-    let total_xoxno = initial_amount + rewards;
-    let expected_lp_amount = additional_amount * initial_amount / total_xoxno;
+    // Calculate expected LP amount, rounded down in favour of the pool
+    let reserve = initial_amount + rewards;
+    let expected_lp_amount = additional_amount * initial_amount / reserve;
+
+    // Only the XOXNO backing the minted LP amount joins the reserve, the remainder is rounding dust
+    let backing = (expected_lp_amount * reserve).div_ceil(initial_amount);
+    let dust = additional_amount - backing;
+    assert_eq!(dust, 1);
 
     // Check the LS token supply after adding more liquidity
     check_ls_token_supply(&mut world, initial_amount + expected_lp_amount);
-    check_virtual_xoxno_reserve(&mut world, initial_amount + rewards + additional_amount);
+    check_virtual_xoxno_reserve(&mut world, reserve + backing);
+    check_rounding_dust(&mut world, dust);
 
     // Add another small reward
     let small_reward = 100_000u64;
     add_rewards(&mut world, small_reward);
-    check_virtual_xoxno_reserve(
-        &mut world,
-        initial_amount + rewards + additional_amount + small_reward,
-    );
+    let reserve = reserve + backing + small_reward;
+    check_virtual_xoxno_reserve(&mut world, reserve);
 
     // Add more liquidity after the small reward
     let small_liquidity = 100_000u64;
     add_liquidity(&mut world, small_liquidity);
 
     // Calculate expected LP amount for the small liquidity addition
-    let total_xoxno_after_rewards = initial_amount + rewards + additional_amount + small_reward;
-    let expected_lp_amount_small =
-        small_liquidity * (initial_amount + expected_lp_amount) / total_xoxno_after_rewards;
+    let supply = initial_amount + expected_lp_amount;
+    let expected_lp_amount_small = small_liquidity * supply / reserve;
+    let backing_small = (expected_lp_amount_small * reserve).div_ceil(supply);
 
     // Check the LS token supply after adding small liquidity
-    check_ls_token_supply(
+    check_ls_token_supply(&mut world, supply + expected_lp_amount_small);
+    check_virtual_xoxno_reserve(&mut world, reserve + backing_small);
+    check_rounding_dust(&mut world, dust + small_liquidity - backing_small);
+}
+
+#[test]
+fn test_add_liquidity_multiple_payments() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    // Delegate three main token payments in a single call
+    add_liquidity_multi(&mut world, &[200u64, 300u64, 500u64]);
+
+    // A single LS payment is minted for the whole batch
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 1000u64);
+    check_ls_token_supply(&mut world, 1000u64);
+    check_virtual_xoxno_reserve(&mut world, 1000u64);
+}
+
+#[test]
+fn test_add_liquidity_split_between_recipients() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    // Split the minted LS tokens 1:2 between the delegator and a second recipient
+    add_liquidity_split(
         &mut world,
-        initial_amount + expected_lp_amount + expected_lp_amount_small,
+        500u64,
+        &[(DELEGATOR_ADDRESS, 1u64), (RECIPIENT_ADDRESS, 2u64)],
     );
-    check_virtual_xoxno_reserve(
+
+    // The last recipient receives the rounding remainder
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 166u64);
+    check_balance(&mut world, RECIPIENT_ADDRESS, LS_TOKEN_ID, 334u64);
+    check_ls_token_supply(&mut world, 500u64);
+    check_virtual_xoxno_reserve(&mut world, 500u64);
+
+    // Zero weights are rejected
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate_split(MultiValueEncoded::from_iter([MultiValue2::from((
+            RECIPIENT_ADDRESS.to_managed_address(),
+            0u64,
+        ))]))
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(100u64),
+        )
+        .returns(ExpectError(4, "Recipient weight must be positive"))
+        .run();
+}
+
+#[test]
+fn test_airdrop_delegation() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    // Store the airdrop entries funded by the owner
+    add_airdrop_entries(
+        &mut world,
+        &[(DELEGATOR_ADDRESS, 100u64), (RECIPIENT_ADDRESS, 200u64)],
+    );
+    check_airdrop_progress(&mut world, 2, 300u64);
+
+    // Nothing is delegated until the airdrop is processed
+    check_ls_token_supply(&mut world, 0u64);
+
+    process_airdrop(&mut world);
+
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 100u64);
+    check_balance(&mut world, RECIPIENT_ADDRESS, LS_TOKEN_ID, 200u64);
+    check_ls_token_supply(&mut world, 300u64);
+    check_virtual_xoxno_reserve(&mut world, 300u64);
+    check_airdrop_progress(&mut world, 0, 0u64);
+
+    // The payment must match the sum of the entries
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .add_airdrop_entries(MultiValueEncoded::from_iter([MultiValue2::from((
+            RECIPIENT_ADDRESS.to_managed_address(),
+            multiversx_sc::proxy_imports::BigUint::from(50u64),
+        ))]))
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(100u64),
+        )
+        .returns(ExpectError(4, "Payment does not match the airdrop entries"))
+        .run();
+}
+
+#[test]
+fn test_cancel_airdrop() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_max_total_reserve(&mut world, 250u64);
+
+    // The entries add up to more than the cap, so the airdrop can never complete
+    add_airdrop_entries(
         &mut world,
-        initial_amount + rewards + additional_amount + small_reward + small_liquidity,
+        &[(DELEGATOR_ADDRESS, 100u64), (RECIPIENT_ADDRESS, 200u64)],
     );
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .process_airdrop()
+        .returns(ExpectError(4, "Total delegation cap exceeded"))
+        .run();
+
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .cancel_airdrop()
+        .returns(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    cancel_airdrop(&mut world);
+    check_airdrop_progress(&mut world, 0, 0u64);
+    check_balance(&mut world, OWNER_ADDRESS, MAIN_TOKEN_ID, 1000u64);
+    check_ls_token_supply(&mut world, 0u64);
+
+    // Queued entries are not redeemable in emergency mode, the owner takes them back instead
+    add_airdrop_entries(&mut world, &[(RECIPIENT_ADDRESS, 200u64)]);
+    set_contract_state(&mut world, State::Emergency);
+    check_balance(&mut world, OWNER_ADDRESS, MAIN_TOKEN_ID, 800u64);
+    cancel_airdrop(&mut world);
+    check_airdrop_progress(&mut world, 0, 0u64);
+    check_balance(&mut world, OWNER_ADDRESS, MAIN_TOKEN_ID, 1000u64);
+}
+
+#[test]
+fn test_early_withdraw_penalty() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    // Early withdraw is rejected until a maximum penalty is configured
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .early_withdraw()
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
+            1u64,
+            &multiversx_sc::proxy_imports::BigUint::from(1u64),
+        )
+        .returns(ExpectError(4, "Early withdraw is not enabled"))
+        .run();
+
+    // 10% penalty right after unstaking
+    set_early_withdraw_max_penalty(&mut world, 1_000u64);
+    check_early_withdraw_penalty(&mut world, 1, 50u64);
+
+    // Halfway through the unbond period the penalty is halved
+    world.set_state_step(SetStateStep::new().block_epoch(6));
+    check_early_withdraw_penalty(&mut world, 1, 25u64);
+
+    early_withdraw_nft(&mut world, 1);
+
+    // The penalty is added back to the reserve for the remaining LS token holders
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 475u64);
+    check_virtual_xoxno_reserve(&mut world, 525u64);
+    check_unstake_token_supply(&mut world, 0u64);
+    check_ls_token_supply(&mut world, 500u64);
+}
+
+#[test]
+fn test_unbond_tiers() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    // Fast tier: 1 epoch at 2%
+    set_unbond_tier(&mut world, 1, 1, 200);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity_with_tier(&mut world, 500u64, 1);
+
+    // The fee stays in the pool for the remaining LS token holders
+    check_virtual_xoxno_reserve(&mut world, 510u64);
+    check_unstake_token_supply(&mut world, 490u64);
+
+    // Unknown tiers are rejected
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::Some(7u8))
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(100u64),
+        )
+        .returns(ExpectError(4, "Unknown unbond tier"))
+        .run();
+
+    // The fast tier NFT can be withdrawn after a single epoch
+    world.set_state_step(SetStateStep::new().block_epoch(2));
+    withdraw_nft(&mut world, 1);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 490u64);
+}
+
+#[test]
+fn test_withdraw_attributes_minted_before_tiers() {
+    let mut world = init_world();
+
+    world.start_trace();
+
+    // An NFT carrying the attributes layout without the unbond tier
+    let mut legacy_attributes = ManagedBuffer::<StaticApi>::new();
+    (
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(300u64),
+        1u64,
+        11u64,
+    )
+        .top_encode(&mut legacy_attributes)
+        .unwrap();
+    world
+        .account(DELEGATOR_ADDRESS)
+        .nonce(1)
+        .esdt_balance(MAIN_TOKEN_ID, 1000u64)
+        .esdt_nft_balance(UNBOUND_TOKEN_ID, 5u64, 1u64, legacy_attributes);
+    world.account(OWNER_ADDRESS).nonce(1);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 5);
+
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 300u64);
+    check_unstake_token_supply(&mut world, 200u64);
+}
+
+#[test]
+fn test_unbond_limit_per_epoch() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    set_max_unbond_per_epoch(&mut world, 300u64);
+    add_liquidity(&mut world, 1000u64);
+
+    // The first request fits into the regular unbond epoch
+    remove_liquidity(&mut world, 200u64);
+    check_remaining_unbond_capacity(&mut world, 11, 100u64);
+    check_unbond_queue_depth(&mut world, 0);
+
+    // The second one is queued into the next epoch
+    remove_liquidity(&mut world, 200u64);
+    check_remaining_unbond_capacity(&mut world, 12, 100u64);
+
+    // Filling the regular unbond epoch increases the queue depth
+    remove_liquidity(&mut world, 100u64);
+    check_remaining_unbond_capacity(&mut world, 11, 0u64);
+    check_unbond_queue_depth(&mut world, 1);
+
+    // Requests above the limit can never be scheduled
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::<u8>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(400u64),
+        )
+        .returns(ExpectError(4, "Amount exceeds the unbond limit per epoch"))
+        .run();
+
+    // The queued NFT carries the later unbond epoch
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .withdraw()
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
+            2u64,
+            &multiversx_sc::proxy_imports::BigUint::from(1u64),
+        )
+        .returns(ExpectError(4, "The unstake period has not passed"))
+        .run();
+
+    world.set_state_step(SetStateStep::new().block_epoch(12));
+    withdraw_nft(&mut world, 2);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 400u64);
+}
+
+#[test]
+fn test_early_withdraw_releases_unbond_capacity() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_max_unbond_per_epoch(&mut world, 300u64);
+    set_early_withdraw_max_penalty(&mut world, 1_000u64);
+    add_liquidity(&mut world, 1000u64);
+
+    // Two full epochs, new requests skip both
+    remove_liquidity(&mut world, 300u64);
+    remove_liquidity(&mut world, 300u64);
+    check_remaining_unbond_capacity(&mut world, 11, 0u64);
+    check_remaining_unbond_capacity(&mut world, 12, 0u64);
+    check_unbond_queue_depth(&mut world, 2);
+
+    // Leaving the queue early frees the capacity of the unbond epoch again
+    early_withdraw_nft(&mut world, 1);
+    check_remaining_unbond_capacity(&mut world, 11, 300u64);
+    check_unbond_queue_depth(&mut world, 0);
+
+    // The penalty kept in the pool makes the 100 LS tokens worth 107 XOXNO
+    remove_liquidity(&mut world, 100u64);
+    check_remaining_unbond_capacity(&mut world, 11, 193u64);
+    check_remaining_unbond_capacity(&mut world, 13, 300u64);
+}
+
+#[test]
+fn test_released_unbond_capacity_is_not_skipped() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_max_unbond_per_epoch(&mut world, 100u64);
+    set_early_withdraw_max_penalty(&mut world, 1u64);
+    add_liquidity(&mut world, 1000u64);
+
+    // The second request is queued into epoch 12, the cursor of epoch 11 then points past it
+    remove_liquidity(&mut world, 100u64);
+    remove_liquidity(&mut world, 100u64);
+    check_unbond_queue_depth(&mut world, 2);
+
+    // Freeing epoch 12 points the cursor of epoch 11 back at it, the 1 XOXNO penalty keeps 100 LS tokens below 101 XOXNO
+    early_withdraw_nft(&mut world, 2);
+    check_remaining_unbond_capacity(&mut world, 12, 100u64);
+    check_unbond_queue_depth(&mut world, 1);
+
+    remove_liquidity(&mut world, 100u64);
+    check_remaining_unbond_capacity(&mut world, 12, 0u64);
+    check_remaining_unbond_capacity(&mut world, 13, 100u64);
+    check_unbond_queue_depth(&mut world, 2);
+
+    // A higher limit opens the full epochs again, the previous cursors are ignored
+    set_max_unbond_per_epoch(&mut world, 200u64);
+    check_unbond_queue_depth(&mut world, 0);
+    remove_liquidity(&mut world, 100u64);
+    check_remaining_unbond_capacity(&mut world, 11, 0u64);
+    check_unbond_queue_depth(&mut world, 1);
+}
+
+#[test]
+fn test_delegation_caps() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    // Unlimited by default
+    check_remaining_total_capacity(&mut world, None);
+    check_remaining_address_capacity(&mut world, DELEGATOR_ADDRESS, None);
+
+    set_max_total_reserve(&mut world, 800u64);
+    set_max_delegation_per_address(&mut world, 300u64);
+
+    add_liquidity(&mut world, 300u64);
+    check_remaining_total_capacity(&mut world, Some(500u64));
+    check_remaining_address_capacity(&mut world, DELEGATOR_ADDRESS, Some(0u64));
+
+    // The per address cap is cumulative
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate(multiversx_sc::proxy_imports::OptionalValue::<TestAddress>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(1u64),
+        )
+        .returns(ExpectError(4, "Address delegation cap exceeded"))
+        .run();
+
+    // Removing the per address cap leaves the total cap in place
+    set_max_delegation_per_address(&mut world, 0u64);
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate(multiversx_sc::proxy_imports::OptionalValue::<TestAddress>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(600u64),
+        )
+        .returns(ExpectError(4, "Total delegation cap exceeded"))
+        .run();
+
+    add_liquidity(&mut world, 500u64);
+    check_remaining_total_capacity(&mut world, Some(0u64));
+    check_virtual_xoxno_reserve(&mut world, 800u64);
+}
+
+#[test]
+fn test_minimum_delegate_amount() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    set_minimum_amounts(&mut world, 100u64, 0u64, 0u64);
+
+    // One unit below the minimum is rejected, the minimum itself is accepted
+    add_liquidity_expect_error(&mut world, 99u64, "Delegate amount below minimum");
+    add_liquidity(&mut world, 100u64);
+    check_ls_token_supply(&mut world, 100u64);
+}
+
+#[test]
+fn test_minimum_undelegate_amount() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    set_minimum_amounts(&mut world, 0u64, 50u64, 0u64);
+    add_liquidity(&mut world, 1000u64);
+
+    // One unit below the minimum is rejected, the minimum itself is accepted
+    remove_liquidity_expect_error(&mut world, 49u64, "Undelegate amount below minimum");
+    remove_liquidity(&mut world, 50u64);
+    check_unstake_token_supply(&mut world, 50u64);
+}
+
+#[test]
+fn test_instant_withdraw_threshold() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    set_minimum_amounts(&mut world, 0u64, 0u64, 60u64);
+    add_liquidity(&mut world, 1000u64);
+
+    // One unit below the threshold is paid out immediately without an unstake token
+    remove_liquidity(&mut world, 59u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 59u64);
+    check_unstake_token_supply(&mut world, 0u64);
+    check_virtual_xoxno_reserve(&mut world, 941u64);
+
+    // The threshold itself goes through the regular unbond period
+    remove_liquidity(&mut world, 60u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 59u64);
+    check_unstake_token_supply(&mut world, 60u64);
+    world
+        .check_account(DELEGATOR_ADDRESS)
+        .esdt_nft_balance_and_attributes(
+            UNBOUND_TOKEN_ID,
+            1u64,
+            1u64,
+            UnstakeTokenAttributes::<StaticApi> {
+                original_amount: BigUint::from(60u64),
+                share_amount: BigUint::from(60u64),
+                unstake_epoch: 1,
+                unbond_epoch: 11,
+                tier_id: 0,
+                unstake_value_index: BigUint::from(INITIAL_EXCHANGE_RATE),
+            },
+        );
+
+    // Instant payouts use the unbond capacity of the current epoch, once it is gone they wait in the queue as well
+    set_max_unbond_per_epoch(&mut world, 100u64);
+    remove_liquidity(&mut world, 50u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 59u64);
+    check_unstake_token_supply(&mut world, 110u64);
+    remove_liquidity(&mut world, 41u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 100u64);
+    check_remaining_unbond_capacity(&mut world, 1, 0u64);
+
+    let stats = get_protocol_stats(&mut world);
+    assert_eq!(stats.total_undelegated, 210u64);
+    assert_eq!(stats.total_withdrawn, 100u64);
+    let reports = get_epoch_reports(&mut world, 0, 1);
+    assert_eq!(reports[0].undelegated, 210u64);
+    assert_eq!(reports[0].withdrawn, 100u64);
+}
+
+#[test]
+fn test_apply_loss_decreases_exchange_rate() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    check_exchange_rate(&mut world, INITIAL_EXCHANGE_RATE);
+
+    // Losses above the default 10% per call are rejected
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .apply_loss(101u64, false)
+        .returns(ExpectError(4, "Loss exceeds the maximum allowed per call"))
+        .run();
+
+    apply_loss(&mut world, 100u64, false);
+    check_virtual_xoxno_reserve(&mut world, 900u64);
+    check_ls_token_supply(&mut world, 1000u64);
+    check_exchange_rate(&mut world, INITIAL_EXCHANGE_RATE / 10 * 9);
+
+    // Unstaking after the loss returns the reduced value
+    remove_liquidity(&mut world, 500u64);
+    check_unstake_token_supply(&mut world, 450u64);
+    check_exchange_rate(&mut world, INITIAL_EXCHANGE_RATE / 10 * 9);
+}
+
+#[test]
+fn test_apply_loss_to_pending_unstakes() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    // The loss is split pro-rata between the reserve and the pending unstakes
+    apply_loss(&mut world, 50u64, true);
+    check_virtual_xoxno_reserve(&mut world, 475u64);
+    check_unstake_token_supply(&mut world, 475u64);
+    check_exchange_rate(&mut world, INITIAL_EXCHANGE_RATE / 100 * 95);
+
+    // Unstake tokens minted after the loss are not affected by it
+    remove_liquidity(&mut world, 100u64);
+    check_unstake_token_supply(&mut world, 570u64);
+
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 475u64);
+    withdraw_nft(&mut world, 2);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 570u64);
+    check_unstake_token_supply(&mut world, 0u64);
+}
+
+#[test]
+fn test_emergency_mode_redemption() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 400u64);
+
+    // Redemption is only possible in emergency mode
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .emergency_redeem()
+        .esdt(TestEsdtTransfer(LS_TOKEN_ID, 0, 100))
+        .returns(ExpectError(4, "Emergency mode is not enabled"))
+        .run();
+
+    set_contract_state(&mut world, State::Emergency);
+    check_contract_state(&mut world, State::Emergency);
+
+    // The emergency mode cannot be switched off and the regular endpoints are disabled
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_state_active()
+        .returns(ExpectError(4, "Emergency mode is enabled"))
+        .run();
+    remove_liquidity_expect_error(&mut world, 100u64, "Not active");
+
+    // LS and unstake tokens are redeemed directly, without waiting for the unbond period
+    emergency_redeem(&mut world, TestEsdtTransfer(LS_TOKEN_ID, 0, 600));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 600u64);
+    emergency_redeem(&mut world, TestEsdtTransfer(UNBOUND_TOKEN_ID, 1, 1));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 1000u64);
+
+    check_ls_token_supply(&mut world, 0u64);
+    check_virtual_xoxno_reserve(&mut world, 0u64);
+    check_unstake_token_supply(&mut world, 0u64);
+}
+
+#[test]
+fn test_emergency_mode_pro_rata_redemption() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract
+    deploy_contract(&mut world);
+
+    // Set the contract state to active
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    // After the loss the claims (450 reserve + 450 pending) no longer match the 1000 balance
+    apply_loss(&mut world, 100u64, true);
+    set_contract_state(&mut world, State::Emergency);
+
+    // Each claim receives its share of the whole balance
+    emergency_redeem(&mut world, TestEsdtTransfer(LS_TOKEN_ID, 0, 250));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 250u64);
+    emergency_redeem(&mut world, TestEsdtTransfer(UNBOUND_TOKEN_ID, 1, 1));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 750u64);
+    emergency_redeem(&mut world, TestEsdtTransfer(LS_TOKEN_ID, 0, 250));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 1000u64);
+}
+
+#[test]
+fn test_upgrade_migrates_storage() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Deploy the contract with the storage layout that predates the storage version
+    deploy_contract(&mut world);
+    check_storage_version(&mut world, 0);
+
+    set_contract_state(&mut world, State::Active);
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 400u64);
+
+    upgrade_contract(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+    check_contract_owner(&mut world, OWNER_ADDRESS);
+
+    // The existing state is preserved and the new mappers are initialised
+    check_contract_state(&mut world, State::Active);
+    check_ls_token_supply(&mut world, 600u64);
+    check_virtual_xoxno_reserve(&mut world, 600u64);
+    check_unstake_token_supply(&mut world, 400u64);
+    check_outstanding_unstake_amount(&mut world, 400u64);
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unstake_value_index()
+        .returns(ExpectValue(INITIAL_EXCHANGE_RATE))
+        .run();
+
+    // Unstake tokens minted before the upgrade can still be withdrawn
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 400u64);
+    check_outstanding_unstake_amount(&mut world, 0u64);
+
+    // Upgrading an already migrated contract is a no-op
+    upgrade_contract(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+}
+
+#[test]
+fn test_upgrade_with_pending_airdrop() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    add_airdrop_entries(
+        &mut world,
+        &[(DELEGATOR_ADDRESS, 100u64), (RECIPIENT_ADDRESS, 200u64)],
+    );
+
+    // The migration neither waits for the airdrop nor touches its entries
+    upgrade_contract(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+    upgrade_contract(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+    check_airdrop_progress(&mut world, 2, 300u64);
+
+    process_airdrop(&mut world);
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 100u64);
+    check_balance(&mut world, RECIPIENT_ADDRESS, LS_TOKEN_ID, 200u64);
+}
+
+#[test]
+fn test_migrate_storage_resumes_pending_steps() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    add_airdrop_entries(
+        &mut world,
+        &[(DELEGATOR_ADDRESS, 100u64), (RECIPIENT_ADDRESS, 200u64)],
+    );
+
+    // Runs the steps an interrupted upgrade left behind, the airdrop keeps its own progress
+    migrate_storage(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+    check_storage_migration_cursor(&mut world, 0);
+    check_contract_owner(&mut world, OWNER_ADDRESS);
+    check_airdrop_progress(&mut world, 2, 300u64);
+
+    process_airdrop(&mut world);
+    check_airdrop_progress(&mut world, 0, 0u64);
+
+    // Nothing is left to migrate afterwards
+    migrate_storage(&mut world);
+    check_storage_version(&mut world, CURRENT_STORAGE_VERSION);
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+    deploy_contract(&mut world);
+
+    // Contracts deployed without a stored owner fall back to the protocol owner
+    check_contract_owner(&mut world, OWNER_ADDRESS);
+
+    propose_owner(&mut world, RECIPIENT_ADDRESS);
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .pending_owner()
+        .returns(ExpectValue(RECIPIENT_ADDRESS))
+        .run();
+
+    // Nothing changes until the proposed owner accepts
+    accept_ownership_expect_error(
+        &mut world,
+        DELEGATOR_ADDRESS,
+        "Caller is not the pending owner",
+    );
+    check_contract_owner(&mut world, OWNER_ADDRESS);
+    set_contract_state(&mut world, State::Inactive);
+
+    let logs = accept_ownership(&mut world, RECIPIENT_ADDRESS);
+    check_contract_owner(&mut world, RECIPIENT_ADDRESS);
+
+    // Both addresses are indexed, the payload adds the block, epoch and timestamp like every other event
+    assert_eq!(logs[0].topics[0], b"ownership_transferred".to_vec());
+    assert_eq!(logs[0].topics[1], OWNER_ADDRESS.to_address().to_vec());
+    assert_eq!(logs[0].topics[2], RECIPIENT_ADDRESS.to_address().to_vec());
+    assert_eq!(logs[0].topics[4].len(), 2 * 32 + 3 * 8);
+
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_state_active()
+        .returns(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    world
+        .tx()
+        .from(RECIPIENT_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_state_active()
+        .run();
+    check_contract_state(&mut world, State::Active);
+}
+
+#[test]
+fn test_cancel_ownership_proposal() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+    deploy_contract(&mut world);
+
+    propose_owner(&mut world, RECIPIENT_ADDRESS);
+    cancel_ownership_proposal(&mut world);
+
+    accept_ownership_expect_error(
+        &mut world,
+        RECIPIENT_ADDRESS,
+        "Caller is not the pending owner",
+    );
+    check_contract_owner(&mut world, OWNER_ADDRESS);
+}
+
+#[test]
+fn test_unstake_token_metadata() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    check_unstake_token_uris(
+        &mut world,
+        &["https://ipfs.io/ipfs/QmY4jtQh6M24uAFR3LcyV7QmL8pkL6zFxXyPXBuzo5sdX5"],
+    );
+    set_unstake_token_uris(
+        &mut world,
+        &["https://xoxno.com/a.png", "https://xoxno.com/a.json"],
+    );
+    check_unstake_token_uris(
+        &mut world,
+        &["https://xoxno.com/a.png", "https://xoxno.com/a.json"],
+    );
+    set_unstake_token_name_template(&mut world, "Unstaked {amount} XOXNO until epoch {epoch}");
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 400u64);
+
+    check_unstake_token_metadata(
+        &mut world,
+        1,
+        r#"{"originalAmount":"400","shareAmount":"400","unstakeEpoch":1,"unbondEpoch":11,"tierId":0}"#,
+    );
+
+    // The attributes still decode for the withdraw
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 400u64);
+
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unstake_token_name_template("x".repeat(101))
+        .returns(ExpectError(4, "Invalid unstake token name template"))
+        .run();
+
+    // An empty list restores the default URI
+    set_unstake_token_uris(&mut world, &[]);
+    check_unstake_token_uris(
+        &mut world,
+        &["https://ipfs.io/ipfs/QmY4jtQh6M24uAFR3LcyV7QmL8pkL6zFxXyPXBuzo5sdX5"],
+    );
+}
+
+fn decode_attributes_layout(
+    encoded: impl TopEncode,
+) -> Result<UnstakeTokenAttributesLayout<StaticApi>, multiversx_sc::codec::DecodeError> {
+    let mut buffer = ManagedBuffer::<StaticApi>::new();
+    encoded.top_encode(&mut buffer).unwrap();
+    UnstakeTokenAttributesLayout::top_decode(buffer)
+}
+
+#[test]
+fn test_unstake_token_attributes_layouts() {
+    let attributes = UnstakeTokenAttributes::<StaticApi> {
+        original_amount: BigUint::from(300u64),
+        share_amount: BigUint::from(250u64),
+        unstake_epoch: 1,
+        unbond_epoch: 11,
+        tier_id: 2,
+        unstake_value_index: BigUint::from(INITIAL_EXCHANGE_RATE / 2),
+    };
+
+    // New attributes are written behind the version byte
+    let mut encoded = ManagedBuffer::<StaticApi>::new();
+    attributes.top_encode(&mut encoded).unwrap();
+    let mut version = [0u8; 1];
+    encoded.load_slice(0, &mut version).unwrap();
+    assert_eq!(version[0], UNSTAKE_TOKEN_ATTRIBUTES_VERSION);
+
+    let layout = decode_attributes_layout(&attributes).unwrap();
+    assert_eq!(layout.version(), UNSTAKE_TOKEN_ATTRIBUTES_VERSION);
+    assert_eq!(layout.into_attributes(), attributes);
+
+    // Layout minted before the version byte, including the tier and the unstake value index
+    let layout = decode_attributes_layout((
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(250u64),
+        1u64,
+        11u64,
+        2u8,
+        BigUint::<StaticApi>::from(INITIAL_EXCHANGE_RATE / 2),
+    ))
+    .unwrap();
+    assert_eq!(layout.version(), UNVERSIONED_ATTRIBUTES_TAG);
+    assert_eq!(layout.into_attributes(), attributes);
+
+    // Layout minted before the unstake value index
+    let layout = decode_attributes_layout((
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(250u64),
+        1u64,
+        11u64,
+        2u8,
+    ))
+    .unwrap();
+    assert_eq!(
+        layout.into_attributes().unstake_value_index,
+        BigUint::<StaticApi>::from(INITIAL_EXCHANGE_RATE)
+    );
+
+    // Layout minted before the unbond tiers
+    let legacy_attributes = decode_attributes_layout((
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(250u64),
+        1u64,
+        11u64,
+    ))
+    .unwrap()
+    .into_attributes();
+    assert_eq!(legacy_attributes.tier_id, 0);
+    assert_eq!(
+        legacy_attributes.unstake_value_index,
+        BigUint::<StaticApi>::from(INITIAL_EXCHANGE_RATE)
+    );
+
+    // Unknown versions and trailing bytes are rejected
+    assert!(decode_attributes_layout((
+        UNSTAKE_TOKEN_ATTRIBUTES_VERSION + 1,
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(250u64),
+        1u64,
+        11u64,
+        2u8,
+        BigUint::<StaticApi>::from(INITIAL_EXCHANGE_RATE / 2),
+    ))
+    .is_err());
+    encoded.append_bytes(&[7u8]);
+    assert!(UnstakeTokenAttributesLayout::<StaticApi>::top_decode(encoded).is_err());
+}
+
+#[test]
+fn test_withdraw_attributes_minted_before_versioning() {
+    let mut world = init_world();
+
+    world.start_trace();
+
+    // An NFT carrying the last layout minted without the version byte
+    let mut unversioned_attributes = ManagedBuffer::<StaticApi>::new();
+    (
+        BigUint::<StaticApi>::from(300u64),
+        BigUint::<StaticApi>::from(300u64),
+        1u64,
+        11u64,
+        0u8,
+        BigUint::<StaticApi>::from(INITIAL_EXCHANGE_RATE),
+    )
+        .top_encode(&mut unversioned_attributes)
+        .unwrap();
+    world
+        .account(DELEGATOR_ADDRESS)
+        .nonce(1)
+        .esdt_balance(MAIN_TOKEN_ID, 1000u64)
+        .esdt_nft_balance(UNBOUND_TOKEN_ID, 5u64, 1u64, unversioned_attributes);
+    world.account(OWNER_ADDRESS).nonce(1);
+
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 5);
+    withdraw_nft(&mut world, 1);
+
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 800u64);
+    check_unstake_token_supply(&mut world, 0u64);
+}
+
+#[test]
+fn test_per_epoch_unstake_tokens() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract_with_unstake_token_mode(&mut world, UnstakeTokenMode::PerEpoch);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+
+    // Unstakes maturing in the same epoch share one nonce, the quantity is the XOXNO amount
+    remove_liquidity(&mut world, 200u64);
+    remove_liquidity(&mut world, 100u64);
+    check_epoch_unstake_nonce(&mut world, 11, 1, 0, 1);
+    world
+        .check_account(DELEGATOR_ADDRESS)
+        .esdt_nft_balance_and_attributes(
+            UNBOUND_TOKEN_ID,
+            1u64,
+            300u64,
+            UnstakeTokenAttributes::<StaticApi> {
+                original_amount: BigUint::from(0u64),
+                share_amount: BigUint::from(0u64),
+                unstake_epoch: 1,
+                unbond_epoch: 11,
+                tier_id: 0,
+                unstake_value_index: BigUint::from(INITIAL_EXCHANGE_RATE),
+            },
+        );
+
+    world.set_state_step(SetStateStep::new().block_epoch(2));
+    remove_liquidity(&mut world, 100u64);
+    check_epoch_unstake_nonce(&mut world, 12, 2, 0, 2);
+    check_epoch_unstake_nonces(&mut world, 11, &[1]);
+    check_epoch_unstake_nonces(&mut world, 12, &[2]);
+    check_unstake_token_supply(&mut world, 400u64);
+
+    // The amounts of a per-epoch position follow the quantity held
+    let position = &get_unstake_position_info(&mut world, &[(1, 120)])[0];
+    assert_eq!(position.original_amount, 120u64);
+    assert_eq!(position.share_amount, 120u64);
+    assert_eq!(position.current_value, 120u64);
+    assert_eq!(position.unbond_epoch, 11);
+
+    // Any quantity of a matured nonce can be withdrawn
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_unstake_tokens(&mut world, 1, 120u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 120u64);
+    check_unstake_token_supply(&mut world, 280u64);
+
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .withdraw()
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
+            2u64,
+            &BigUint::from(100u64),
+        )
+        .returns(ExpectError(4, "The unstake period has not passed"))
+        .run();
+
+    world.set_state_step(SetStateStep::new().block_epoch(12));
+    withdraw_unstake_tokens(&mut world, 1, 180u64);
+    withdraw_unstake_tokens(&mut world, 2, 100u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 400u64);
+    check_unstake_token_supply(&mut world, 0u64);
+}
+
+#[test]
+fn test_per_epoch_unstake_tokens_after_loss() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract_with_unstake_token_mode(&mut world, UnstakeTokenMode::PerEpoch);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 500u64);
+
+    // A loss on the pending unstakes changes the value index, so the epoch moves to a new nonce
+    apply_loss(&mut world, 100u64, true);
+    remove_liquidity(&mut world, 200u64);
+    check_epoch_unstake_nonce(&mut world, 11, 1, 0, 2);
+    check_epoch_unstake_nonces(&mut world, 11, &[1, 2]);
+
+    // The first nonce carries the haircut, the second one was minted at the reduced exchange rate
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_unstake_tokens(&mut world, 1, 500u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 450u64);
+    withdraw_unstake_tokens(&mut world, 2, 180u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 630u64);
+}
+
+#[test]
+fn test_per_epoch_unstake_tokens_keep_own_penalty() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract_with_unstake_token_mode(&mut world, UnstakeTokenMode::PerEpoch);
+    set_contract_state(&mut world, State::Active);
+    set_unbond_tier(&mut world, 1, 5, 0);
+    set_early_withdraw_max_penalty(&mut world, 1_000u64);
+
+    add_liquidity(&mut world, 1000u64);
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate(OptionalValue::<TestAddress>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &BigUint::from(1000u64),
+        )
+        .run();
+
+    // Both unstakes mature at epoch 11, the first one over the default tier and the second one over the short tier
+    remove_liquidity(&mut world, 500u64);
+    world.set_state_step(SetStateStep::new().block_epoch(6));
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(OptionalValue::Some(1u8))
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
+            0u64,
+            &BigUint::from(500u64),
+        )
+        .run();
+    check_epoch_unstake_nonce(&mut world, 11, 1, 0, 1);
+    check_epoch_unstake_nonce(&mut world, 11, 6, 1, 2);
+    check_epoch_unstake_nonces(&mut world, 11, &[1, 2]);
+    world
+        .check_account(OWNER_ADDRESS)
+        .esdt_balance(LS_TOKEN_ID, 500u64)
+        .esdt_nft_balance_and_attributes(
+            UNBOUND_TOKEN_ID,
+            2u64,
+            500u64,
+            UnstakeTokenAttributes::<StaticApi> {
+                original_amount: BigUint::from(0u64),
+                share_amount: BigUint::from(0u64),
+                unstake_epoch: 6,
+                unbond_epoch: 11,
+                tier_id: 1,
+                unstake_value_index: BigUint::from(INITIAL_EXCHANGE_RATE),
+            },
+        );
+
+    // Halfway through the default tier the penalty is halved, the short tier just started
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_early_withdraw_penalty(1u64, OptionalValue::Some(BigUint::from(500u64)))
+        .returns(ExpectValue(25u64))
+        .run();
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_early_withdraw_penalty(2u64, OptionalValue::Some(BigUint::from(500u64)))
+        .returns(ExpectValue(50u64))
+        .run();
+
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .early_withdraw()
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
+            2u64,
+            &BigUint::from(500u64),
+        )
+        .run();
+    check_balance(&mut world, OWNER_ADDRESS, MAIN_TOKEN_ID, 450u64);
+}
+
+#[test]
+fn test_replace_ls_token() {
+    const NEW_LS_TOKEN_ID: TestTokenIdentifier = TestTokenIdentifier::new("LXOXNO-654321");
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    check_missing_token_roles(&mut world, &[]);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 200u64);
+
+    // A token without the local roles is reported by the roles view
+    set_ls_token_id(&mut world, NEW_LS_TOKEN_ID);
+    check_missing_token_roles(
+        &mut world,
+        &[
+            ("LXOXNO-654321", "ESDTRoleLocalMint"),
+            ("LXOXNO-654321", "ESDTRoleLocalBurn"),
+        ],
+    );
+    world.set_esdt_local_roles(
+        RS_LIQUIDXOXNO_ADDRESS,
+        b"LXOXNO-654321",
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+    check_missing_token_roles(&mut world, &[]);
+
+    // The previous LS token can no longer be undelegated, only swapped 1:1
+    remove_liquidity_expect_error(&mut world, 100u64, "Bad payment token");
+    migrate_ls_token(&mut world, LS_TOKEN_ID, 600u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 200u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, NEW_LS_TOKEN_ID, 600u64);
+    check_ls_token_supply(&mut world, 800u64);
+
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(OptionalValue::<u8>::None)
+        .payment(TestEsdtTransfer(NEW_LS_TOKEN_ID, 0, 600u64))
+        .run();
+    check_ls_token_supply(&mut world, 200u64);
+    check_unstake_token_supply(&mut world, 800u64);
+
+    // The unstake token cannot be replaced while unstake tokens are outstanding
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unstake_token_id(
+            TestTokenIdentifier::new("UXOXNO-654321"),
+            OptionalValue::<UnstakeTokenMode>::None,
+        )
+        .returns(ExpectError(4, "Unstake tokens are still outstanding"))
+        .run();
+
+    // The swap is paused with the other user endpoints, but stays open in emergency mode
+    set_contract_state(&mut world, State::Inactive);
+    migrate_ls_token_expect_error(&mut world, LS_TOKEN_ID, 100u64, "Not active");
+    set_contract_state(&mut world, State::Emergency);
+    migrate_ls_token(&mut world, LS_TOKEN_ID, 100u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, NEW_LS_TOKEN_ID, 100u64);
+}
+
+#[test]
+fn test_replace_unstake_token() {
+    const NEW_UNSTAKE_TOKEN_ID: TestTokenIdentifier = TestTokenIdentifier::new("UXOXNO-654321");
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 300u64);
+    remove_liquidity(&mut world, 400u64);
+    apply_loss(&mut world, 7u64, true);
+    check_outstanding_unstake_amount(&mut world, 700u64);
+
+    // Each position floors its value, so the unstake supply keeps a residue after all are withdrawn
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    withdraw_nft(&mut world, 2);
+    check_unstake_token_supply(&mut world, 1u64);
+    check_outstanding_unstake_amount(&mut world, 0u64);
+
+    // Per-epoch tokens need a semi-fungible token, which is the only one with the add quantity role
+    set_unstake_token_id_expect_error(
+        &mut world,
+        NEW_UNSTAKE_TOKEN_ID,
+        UnstakeTokenMode::PerEpoch,
+        "Unstake token type does not match the mode",
+    );
+    world.set_esdt_local_roles(
+        RS_LIQUIDXOXNO_ADDRESS,
+        b"UXOXNO-654321",
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftBurn,
+            EsdtLocalRole::NftAddQuantity,
+        ],
+    );
+    set_unstake_token_id_expect_error(
+        &mut world,
+        NEW_UNSTAKE_TOKEN_ID,
+        UnstakeTokenMode::PerPosition,
+        "Unstake token type does not match the mode",
+    );
+    set_unstake_token_id(&mut world, NEW_UNSTAKE_TOKEN_ID, UnstakeTokenMode::PerEpoch);
+    check_missing_token_roles(&mut world, &[]);
+}
+
+#[test]
+fn test_storage_cache_writes_only_modified_fields() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+
+    // Only checks which fields each endpoint writes back, the Rust VM does not meter gas,
+    // so the gas used before and after the storage cache change is not compared here
+
+    // Leading zero bytes decode to the same amount, but are dropped as soon as the value is written back
+    deploy_contract_with_raw_storage(
+        &mut world,
+        UnstakeTokenMode::PerPosition,
+        &[
+            ("lsTokenSupply", &[0x00, 0x03, 0xe8]),
+            ("virtualXOXNOReserve", &[0x00, 0x03, 0xe8]),
+            ("unstakeTokenSupply", &[0x00]),
+        ],
+    );
+    set_contract_state(&mut world, State::Active);
+
+    // Rewards only change the reserve
+    add_rewards(&mut world, 100u64);
+    check_raw_storage(&mut world, "lsTokenSupply", &[0x00, 0x03, 0xe8]);
+    check_raw_storage(&mut world, "virtualXOXNOReserve", &[0x04, 0x4c]);
+    check_ls_token_supply(&mut world, 1000u64);
+    check_virtual_xoxno_reserve(&mut world, 1100u64);
+
+    // Delegating changes both, the unstaked amount is never written
+    add_liquidity(&mut world, 110u64);
+    check_raw_storage(&mut world, "lsTokenSupply", &[0x04, 0x4c]);
+    check_raw_storage(&mut world, "virtualXOXNOReserve", &[0x04, 0xba]);
+    check_raw_storage(&mut world, "unstakeTokenSupply", &[0x00]);
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 100u64);
+
+    // Undelegating changes all three
+    remove_liquidity(&mut world, 50u64);
+    check_raw_storage(&mut world, "lsTokenSupply", &[0x04, 0x1a]);
+    check_raw_storage(&mut world, "virtualXOXNOReserve", &[0x04, 0x83]);
+    check_raw_storage(&mut world, "unstakeTokenSupply", &[0x37]);
+
+    // Withdrawing only releases the unstaked amount
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+    check_raw_storage(&mut world, "lsTokenSupply", &[0x04, 0x1a]);
+    check_raw_storage(&mut world, "virtualXOXNOReserve", &[0x04, 0x83]);
+    check_raw_storage(&mut world, "unstakeTokenSupply", &[]);
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 945u64);
+}
+
+#[test]
+fn test_offchain_pricing_matches_contract() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_unbond_tier(&mut world, 1, 10, 250);
+
+    let mut pool = OffchainPool::default();
+    let mut ls_token_balance = num_bigint::BigUint::ZERO;
+    let mut unstaked_amount = num_bigint::BigUint::ZERO;
+    check_offchain_pool(&mut world, &pool);
+
+    // Amounts picked so that almost every step has to round
+    for (delegated, rewards, undelegated, tier_id) in [
+        (500u64, 333u64, 13u64, 0u8),
+        (101, 0, 77, 1),
+        (7, 1, 199, 1),
+        (250, 17, 3, 0),
+    ] {
+        add_liquidity(&mut world, delegated);
+        ls_token_balance += pool.delegate(&delegated.into()).unwrap();
+        check_offchain_pool(&mut world, &pool);
+
+        if rewards > 0 {
+            add_rewards(&mut world, rewards);
+            pool.add_rewards(&rewards.into());
+            check_offchain_pool(&mut world, &pool);
+        }
+
+        remove_liquidity_with_tier(&mut world, undelegated, tier_id);
+        let tier_fee = if tier_id == 1 { 250 } else { 0 };
+        let undelegation = pool.undelegate(&undelegated.into(), tier_fee).unwrap();
+        ls_token_balance -= undelegated;
+        unstaked_amount += undelegation.xoxno_to_unstake;
+        check_offchain_pool(&mut world, &pool);
+    }
+
+    check_balance(
+        &mut world,
+        DELEGATOR_ADDRESS,
+        LS_TOKEN_ID,
+        u64::try_from(&ls_token_balance).unwrap(),
+    );
+    check_unstake_token_supply(&mut world, u64::try_from(&unstaked_amount).unwrap());
+}
+
+#[test]
+fn test_sweep_rounding_dust() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    let mut pool = OffchainPool::default();
+    add_liquidity(&mut world, 500u64);
+    pool.delegate(&500u64.into()).unwrap();
+    add_rewards(&mut world, 333u64);
+    pool.add_rewards(&333u64.into());
+
+    // 101 XOXNO mint 60 LS tokens, which are backed by 100 XOXNO at the current rate
+    add_liquidity(&mut world, 101u64);
+    pool.delegate(&101u64.into()).unwrap();
+    check_offchain_pool(&mut world, &pool);
+    check_rounding_dust(&mut world, 1u64);
+
+    // The last LS token holder leaving redeems the whole reserve, but not the dust
+    remove_liquidity(&mut world, 560u64);
+    pool.undelegate(&560u64.into(), 0).unwrap();
+    check_offchain_pool(&mut world, &pool);
+    check_virtual_xoxno_reserve(&mut world, 0u64);
+    check_unstake_token_supply(&mut world, 933u64);
+    world
+        .check_account(RS_LIQUIDXOXNO_ADDRESS)
+        .esdt_balance(MAIN_TOKEN_ID, 934u64);
+
+    sweep_rounding_dust_expect_error(&mut world, "Treasury address not set");
+    set_treasury(&mut world, RECIPIENT_ADDRESS);
+    sweep_rounding_dust(&mut world, 1u64);
+    pool.sweep_rounding_dust().unwrap();
+    check_offchain_pool(&mut world, &pool);
+    check_balance(&mut world, RECIPIENT_ADDRESS, MAIN_TOKEN_ID, 1u64);
+    world
+        .check_account(RS_LIQUIDXOXNO_ADDRESS)
+        .esdt_balance(MAIN_TOKEN_ID, 933u64);
+
+    sweep_rounding_dust_expect_error(&mut world, "No rounding dust to sweep");
+}
+
+#[test]
+fn test_exchange_rate_views() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_unbond_tier(&mut world, 1, 10, 1_000);
+
+    // Rewards sent to an empty pool do not change the rate the first delegation mints at
+    add_rewards(&mut world, 100u64);
+    check_exchange_rate(&mut world, INITIAL_EXCHANGE_RATE);
+    check_exchange_rate_fraction(&mut world, 1u64, 1u64);
+    check_exchange_rate_with_precision(&mut world, 6, 1_000_000u64);
+
+    // They are owed to the treasury instead of the first delegator
+    add_liquidity(&mut world, 500u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 500u64);
+    check_virtual_xoxno_reserve(&mut world, 500u64);
+    check_rounding_dust(&mut world, 100u64);
+
+    add_rewards(&mut world, 333u64);
+    check_exchange_rate_fraction(&mut world, 833u64, 500u64);
+    check_exchange_rate_with_precision(&mut world, 0, 1u64);
+    check_exchange_rate_with_precision(&mut world, 6, 1_666_000u64);
+    check_exchange_rate_with_precision(&mut world, 18, 1_666_000_000_000_000_000u64);
+    check_exchange_rate(&mut world, 1_666_000_000_000_000_000u64);
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_exchange_rate_with_precision(37u32)
+        .returns(ExpectError(4, "Exchange rate precision too high"))
+        .run();
+
+    // The tier fee paid by the last LS token holder stays in the reserve without any supply left
+    remove_liquidity_with_tier(&mut world, 500u64, 1);
+    check_virtual_xoxno_reserve(&mut world, 84u64);
+    check_ls_token_supply(&mut world, 0u64);
+    check_exchange_rate_fraction(&mut world, 1u64, 1u64);
+
+    add_liquidity(&mut world, 100u64);
+    check_balance(&mut world, DELEGATOR_ADDRESS, LS_TOKEN_ID, 100u64);
+    check_virtual_xoxno_reserve(&mut world, 100u64);
+    check_rounding_dust(&mut world, 184u64);
+}
+
+#[test]
+fn test_contract_summary() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+
+    let summary = get_contract_summary(&mut world);
+    assert_eq!(summary.state, State::Inactive);
+    assert_eq!(summary.ls_token_supply, 0u64);
+    assert_eq!(summary.exchange_rate, INITIAL_EXCHANGE_RATE);
+    assert_eq!(summary.contract_balance, 0u64);
+
+    set_contract_state(&mut world, State::Active);
+    add_liquidity(&mut world, 500u64);
+    add_rewards(&mut world, 100u64);
+    remove_liquidity(&mut world, 100u64);
+    world.set_state_step(SetStateStep::new().block_epoch(3));
+
+    let summary = get_contract_summary(&mut world);
+    assert_eq!(summary.state, State::Active);
+    assert_eq!(summary.main_token_id, MAIN_TOKEN_ID.to_token_identifier());
+    assert_eq!(summary.ls_token_id, Some(LS_TOKEN_ID.to_token_identifier()));
+    assert_eq!(
+        summary.unstake_token_id,
+        Some(UNBOUND_TOKEN_ID.to_token_identifier())
+    );
+    assert_eq!(summary.unstake_token_mode, UnstakeTokenMode::PerPosition);
+    assert_eq!(summary.ls_token_supply, 400u64);
+    assert_eq!(summary.virtual_xoxno_reserve, 480u64);
+    assert_eq!(summary.unstake_token_supply, 120u64);
+    assert_eq!(summary.rounding_dust, 0u64);
+    assert_eq!(summary.exchange_rate, 1_200_000_000_000_000_000u64);
+    assert_eq!(summary.unbond_period, 10);
+    assert_eq!(summary.current_epoch, 3);
+    assert_eq!(summary.contract_balance, 600u64);
+}
+
+#[test]
+fn test_quote_views() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+
+    assert_eq!(
+        quote_delegate(&mut world, 100u64, None),
+        quote(0, 0, INITIAL_EXCHANGE_RATE, 0, QuoteError::NotActive)
+    );
+
+    set_contract_state(&mut world, State::Active);
+    assert_eq!(
+        quote_delegate(&mut world, 0u64, None),
+        quote(0, 0, INITIAL_EXCHANGE_RATE, 0, QuoteError::BadPaymentAmount)
+    );
+    assert_eq!(
+        quote_delegate(&mut world, 500u64, None),
+        quote(500, 0, INITIAL_EXCHANGE_RATE, 0, QuoteError::None)
+    );
+    add_liquidity(&mut world, 500u64);
+    add_rewards(&mut world, 100u64);
+
+    // 7 XOXNO mint 5 LS tokens worth 6 XOXNO, the remaining unit is rounding dust
+    assert_eq!(
+        quote_delegate(&mut world, 7u64, None),
+        quote(5, 1, 1_200_000_000_000_000_000, 0, QuoteError::None)
+    );
+
+    set_max_total_reserve(&mut world, 650u64);
+    set_max_delegation_per_address(&mut world, 600u64);
+    assert_eq!(
+        quote_delegate(&mut world, 60u64, None).error,
+        QuoteError::TotalCapExceeded
+    );
+    assert_eq!(
+        quote_delegate(&mut world, 40u64, Some(DELEGATOR_ADDRESS)).error,
+        QuoteError::None
+    );
+    set_max_total_reserve(&mut world, 1_000_000u64);
+    assert_eq!(
+        quote_delegate(&mut world, 200u64, Some(DELEGATOR_ADDRESS)).error,
+        QuoteError::AddressCapExceeded
+    );
+    assert_eq!(
+        quote_delegate(&mut world, 200u64, None).error,
+        QuoteError::None
+    );
+
+    assert_eq!(
+        quote_un_delegate(&mut world, 600u64, None),
+        quote(
+            0,
+            0,
+            1_200_000_000_000_000_000,
+            0,
+            QuoteError::NotEnoughLsSupply
+        )
+    );
+    assert_eq!(
+        quote_un_delegate(&mut world, 100u64, Some(9)).error,
+        QuoteError::UnknownUnbondTier
+    );
+
+    // 100 LS tokens are worth 120 XOXNO, the 10% fee stays in the pool
+    set_unbond_tier(&mut world, 1, 5, 1_000);
+    assert_eq!(
+        quote_un_delegate(&mut world, 100u64, Some(1)),
+        quote(108, 12, 1_230_000_000_000_000_000, 6, QuoteError::None)
+    );
+    remove_liquidity_with_tier(&mut world, 100u64, 1);
+    check_unstake_token_supply(&mut world, 108u64);
+    check_exchange_rate(&mut world, 1_230_000_000_000_000_000u64);
+
+    // 61 XOXNO minus a 7 XOXNO fee no longer fit next to the 108 XOXNO unbonding at epoch 6
+    set_max_unbond_per_epoch(&mut world, 150u64);
+    assert_eq!(
+        quote_un_delegate(&mut world, 50u64, Some(1)),
+        quote(54, 7, 1_251_428_571_428_571_428, 7, QuoteError::None)
+    );
+    assert_eq!(
+        quote_un_delegate(&mut world, 200u64, None).error,
+        QuoteError::UnbondLimitExceeded
+    );
+
+    set_minimum_amounts(&mut world, 10u64, 100u64, 20u64);
+    assert_eq!(
+        quote_delegate(&mut world, 5u64, None).error,
+        QuoteError::DelegateAmountTooLow
+    );
+    assert_eq!(
+        quote_un_delegate(&mut world, 50u64, None).error,
+        QuoteError::UndelegateAmountTooLow
+    );
+
+    // Below the instant withdraw threshold the XOXNO is paid out right away
+    set_minimum_amounts(&mut world, 10u64, 0u64, 20u64);
+    assert_eq!(
+        quote_un_delegate(&mut world, 10u64, None),
+        quote(12, 0, 1_230_769_230_769_230_769, 1, QuoteError::None)
+    );
+
+    assert_eq!(
+        quote_withdraw(&mut world, &[]).error,
+        QuoteError::BadPaymentAmount
+    );
+    assert_eq!(
+        quote_withdraw(&mut world, &[(9, 1)]).error,
+        QuoteError::UnknownPosition
+    );
+    assert_eq!(
+        quote_withdraw(&mut world, &[(1, 1)]).error,
+        QuoteError::UnstakePeriodNotPassed
+    );
+
+    world.set_state_step(SetStateStep::new().block_epoch(6));
+    assert_eq!(
+        quote_withdraw(&mut world, &[(1, 1)]),
+        quote(108, 0, 1_230_000_000_000_000_000, 6, QuoteError::None)
+    );
+    withdraw_nft(&mut world, 1);
+    check_unstake_token_supply(&mut world, 0u64);
+}
+
+#[test]
+fn test_unstake_position_info() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 500u64);
+    remove_liquidity(&mut world, 100u64);
+    remove_liquidity(&mut world, 50u64);
+
+    let positions = get_unstake_position_info(&mut world, &[(2, 1), (1, 1)]);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0].nonce, 2);
+    assert_eq!(positions[0].original_amount, 50u64);
+    let position = &positions[1];
+    assert_eq!(position.nonce, 1);
+    assert_eq!(position.original_amount, 100u64);
+    assert_eq!(position.share_amount, 100u64);
+    assert_eq!(position.current_value, 100u64);
+    assert_eq!(position.haircut_amount, 0u64);
+    assert_eq!(position.haircut, 0);
+    assert_eq!(position.unstake_epoch, 1);
+    assert_eq!(position.unbond_epoch, 11);
+    assert_eq!(position.epochs_remaining, 10);
+    assert_eq!(position.tier_id, 0);
+    assert!(!position.withdrawable);
+
+    // 12 of the 150 XOXNO pending take the loss, an 8% haircut
+    apply_loss(&mut world, 40u64, true);
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+
+    let position = &get_unstake_position_info(&mut world, &[(1, 1)])[0];
+    assert_eq!(position.current_value, 92u64);
+    assert_eq!(position.haircut_amount, 8u64);
+    assert_eq!(position.haircut, 800);
+    assert_eq!(position.epochs_remaining, 0);
+    assert!(position.withdrawable);
+
+    set_contract_state(&mut world, State::Inactive);
+    assert!(!get_unstake_position_info(&mut world, &[(1, 1)])[0].withdrawable);
+
+    get_unstake_position_info_expect_error(
+        &mut world,
+        &[(1, 1), (7, 1)],
+        "Unknown unstake token nonce",
+    );
+
+    // The positions are read from the contract storage, which forgets them once withdrawn
+    set_contract_state(&mut world, State::Active);
+    withdraw_nft(&mut world, 2);
+    get_unstake_position_info_expect_error(&mut world, &[(2, 1)], "Unknown unstake token nonce");
+}
+
+#[test]
+fn test_per_epoch_unstake_position_info() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract_with_unstake_token_mode(&mut world, UnstakeTokenMode::PerEpoch);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 200u64);
+    add_rewards(&mut world, 1000u64);
+    remove_liquidity(&mut world, 100u64);
+    check_epoch_unstake_nonces(&mut world, 11, &[1]);
+
+    // 425 XOXNO were unstaked for 300 LS on the nonce, each quantity gets its share of both
+    let positions = get_unstake_position_info(&mut world, &[(1, 85), (1, 425)]);
+    assert_eq!(positions[0].original_amount, 85u64);
+    assert_eq!(positions[0].share_amount, 60u64);
+    assert_eq!(positions[0].current_value, 85u64);
+    assert_eq!(positions[1].original_amount, 425u64);
+    assert_eq!(positions[1].share_amount, 300u64);
+    assert_eq!(positions[1].unbond_epoch, 11);
+    assert!(!positions[1].withdrawable);
+}
+
+#[test]
+fn test_protocol_stats() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    let stats = get_protocol_stats(&mut world);
+    assert_eq!(stats.total_delegated, 0u64);
+    assert_eq!(stats.delegate_count, 0);
+    assert_eq!(stats.unique_beneficiaries, 0);
+
+    add_liquidity(&mut world, 500u64);
+    add_liquidity_split(
+        &mut world,
+        100u64,
+        &[(DELEGATOR_ADDRESS, 1), (RECIPIENT_ADDRESS, 1)],
+    );
+    add_rewards(&mut world, 60u64);
+    remove_liquidity(&mut world, 100u64);
+    world.set_state_step(SetStateStep::new().block_epoch(11));
+    withdraw_nft(&mut world, 1);
+
+    let stats = get_protocol_stats(&mut world);
+    assert_eq!(stats.total_delegated, 600u64);
+    assert_eq!(stats.total_undelegated, 110u64);
+    assert_eq!(stats.total_withdrawn, 110u64);
+    assert_eq!(stats.total_rewards, 60u64);
+    assert_eq!(stats.delegate_count, 2);
+    assert_eq!(stats.undelegate_count, 1);
+    assert_eq!(stats.unique_beneficiaries, 2);
+}
+
+#[test]
+fn test_unique_beneficiaries_with_zero_staked_share() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    world.account(RECIPIENT_ADDRESS).nonce(1);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+
+    add_liquidity(&mut world, 900u64);
+    apply_loss(&mut world, 90u64, false);
+
+    // Below an exchange rate of 1 the recipient gets 1 LS for a staked share of 0, and is still counted once
+    for _ in 0..2 {
+        add_liquidity_split(
+            &mut world,
+            10u64,
+            &[(RECIPIENT_ADDRESS, 1), (DELEGATOR_ADDRESS, 10)],
+        );
+    }
+    check_balance(&mut world, RECIPIENT_ADDRESS, LS_TOKEN_ID, 2u64);
+    assert_eq!(get_protocol_stats(&mut world).unique_beneficiaries, 2);
+}
+
+#[test]
+fn test_epoch_reports() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    check_epoch_report_count(&mut world, 0);
+
+    add_liquidity(&mut world, 500u64);
+    add_rewards(&mut world, 100u64);
+    world.set_state_step(SetStateStep::new().block_epoch(2));
+    remove_liquidity(&mut world, 100u64);
+    world.set_state_step(SetStateStep::new().block_epoch(12));
+    withdraw_nft(&mut world, 1);
+
+    check_epoch_report_count(&mut world, 3);
+    let reports = get_epoch_reports(&mut world, 0, 10);
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports[0].epoch, 1);
+    assert_eq!(reports[0].delegated, 500u64);
+    assert_eq!(reports[0].rewards, 100u64);
+    assert_eq!(reports[0].start_exchange_rate, INITIAL_EXCHANGE_RATE);
+    assert_eq!(reports[0].end_exchange_rate, 1_200_000_000_000_000_000u64);
+    assert_eq!(reports[1].epoch, 2);
+    assert_eq!(reports[1].undelegated, 120u64);
+    assert_eq!(reports[1].start_exchange_rate, 1_200_000_000_000_000_000u64);
+    assert_eq!(reports[2].epoch, 12);
+    assert_eq!(reports[2].withdrawn, 120u64);
+    assert_eq!(reports[2].delegated, 0u64);
+
+    assert_eq!(get_epoch_reports(&mut world, 1, 1), reports[1..2].to_vec());
+    assert!(get_epoch_reports(&mut world, 3, 10).is_empty());
+
+    // Once the buffer is full every new epoch overwrites the oldest report
+    for epoch in 13..378 {
+        world.set_state_step(SetStateStep::new().block_epoch(epoch));
+        add_rewards(&mut world, 1u64);
+    }
+    check_epoch_report_count(&mut world, 368);
+    let reports = get_epoch_reports(&mut world, 0, 2);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].epoch, 13);
+    assert_eq!(reports[1].epoch, 14);
+    assert_eq!(reports[1].start_exchange_rate, reports[0].end_exchange_rate);
+    assert_eq!(get_epoch_reports(&mut world, 367, 10)[0].epoch, 377);
+}
+
+#[test]
+fn test_epoch_reports_include_instant_payouts_and_redemptions() {
+    let mut world = init_world();
+
+    world.start_trace();
+    set_users(&mut world);
+    deploy_contract(&mut world);
+    set_contract_state(&mut world, State::Active);
+    set_minimum_amounts(&mut world, 0u64, 0u64, 60u64);
+
+    add_liquidity(&mut world, 1000u64);
+    remove_liquidity(&mut world, 50u64);
+    remove_liquidity(&mut world, 100u64);
+
+    world.set_state_step(SetStateStep::new().block_epoch(2));
+    set_contract_state(&mut world, State::Emergency);
+    emergency_redeem(&mut world, TestEsdtTransfer(LS_TOKEN_ID, 0, 850));
+    emergency_redeem(&mut world, TestEsdtTransfer(UNBOUND_TOKEN_ID, 1, 1));
+    check_balance(&mut world, DELEGATOR_ADDRESS, MAIN_TOKEN_ID, 1000u64);
+
+    let reports = get_epoch_reports(&mut world, 0, 10);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].delegated, 1000u64);
+    assert_eq!(reports[0].undelegated, 150u64);
+    assert_eq!(reports[0].withdrawn, 50u64);
+    assert_eq!(reports[1].epoch, 2);
+    assert_eq!(reports[1].withdrawn, 950u64);
+    assert_eq!(get_protocol_stats(&mut world).total_withdrawn, 1000u64);
 }
@@ -0,0 +1,342 @@
+use multiversx_sc::types::BigUint;
+use multiversx_sc_scenario::api::StaticApi;
+use rs_liquid_xoxno::config::INITIAL_EXCHANGE_RATE;
+use rs_liquid_xoxno::errors::*;
+use rs_liquid_xoxno::offchain::OffchainPool;
+use rs_liquid_xoxno::pricing::{self, PoolSnapshot};
+
+fn pool(ls_token_supply: u64, virtual_xoxno_reserve: u64) -> PoolSnapshot<StaticApi> {
+    PoolSnapshot {
+        ls_token_supply: BigUint::from(ls_token_supply),
+        virtual_xoxno_reserve: BigUint::from(virtual_xoxno_reserve),
+    }
+}
+
+fn amount(value: u64) -> BigUint<StaticApi> {
+    BigUint::from(value)
+}
+
+#[test]
+fn test_ls_token_amount_for_xoxno() {
+    // An empty pool mints 1:1, even with reserve left without any LS token
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(500), &pool(0, 0)),
+        Ok(amount(500))
+    );
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(500), &pool(0, 84)),
+        Ok(amount(500))
+    );
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(110), &pool(1000, 1100)),
+        Ok(amount(100))
+    );
+
+    // Rounded down in favour of the pool
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(100), &pool(1000, 1100)),
+        Ok(amount(90))
+    );
+
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(0), &pool(1000, 1000)),
+        Err(ERROR_INSUFFICIENT_LIQ_BURNED)
+    );
+    assert_eq!(
+        pricing::ls_token_amount_for_xoxno(&amount(1), &pool(1000, 3000)),
+        Err(ERROR_INSUFFICIENT_LIQUIDITY)
+    );
+}
+
+#[test]
+fn test_xoxno_amount_for_ls_token() {
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(100), &pool(1000, 1100)),
+        Ok(amount(110))
+    );
+
+    // Rounded down in favour of the pool
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(7), &pool(1000, 1100)),
+        Ok(amount(7))
+    );
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(1000), &pool(1000, 1100)),
+        Ok(amount(1100))
+    );
+
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(1001), &pool(1000, 1100)),
+        Err(ERROR_NOT_ENOUGH_LP)
+    );
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(0), &pool(0, 0)),
+        Err(ERROR_INSUFFICIENT_LIQ_BURNED)
+    );
+    assert_eq!(
+        pricing::xoxno_amount_for_ls_token(&amount(1), &pool(1000, 500)),
+        Err(ERROR_INSUFFICIENT_LIQ_BURNED)
+    );
+}
+
+#[test]
+fn test_exchange_rate() {
+    assert_eq!(
+        pricing::exchange_rate(&pool(0, 0)),
+        amount(INITIAL_EXCHANGE_RATE)
+    );
+    assert_eq!(
+        pricing::exchange_rate(&pool(1000, 1000)),
+        amount(INITIAL_EXCHANGE_RATE)
+    );
+    assert_eq!(
+        pricing::exchange_rate(&pool(1000, 1100)),
+        amount(INITIAL_EXCHANGE_RATE / 10 * 11)
+    );
+    assert_eq!(
+        pricing::exchange_rate(&pool(0, 84)),
+        amount(INITIAL_EXCHANGE_RATE)
+    );
+
+    assert_eq!(
+        pricing::exchange_rate_fraction(&pool(0, 84)),
+        (amount(1), amount(1))
+    );
+    assert_eq!(
+        pricing::exchange_rate_fraction(&pool(3, 10)),
+        (amount(10), amount(3))
+    );
+    assert_eq!(
+        pricing::exchange_rate_with_precision(&pool(3, 10), 0),
+        amount(3)
+    );
+    assert_eq!(
+        pricing::exchange_rate_with_precision(&pool(3, 10), 4),
+        amount(33_333)
+    );
+}
+
+#[test]
+fn test_delegate_then_undelegate_never_gains() {
+    let mut pool = pool(1000, 1100);
+    for delegated in [2u64, 3, 11, 100, 999, 12345] {
+        let ls_token_amount =
+            pricing::ls_token_amount_for_xoxno(&amount(delegated), &pool).unwrap();
+        pool.ls_token_supply += &ls_token_amount;
+        pool.virtual_xoxno_reserve += delegated;
+
+        let released = pricing::xoxno_amount_for_ls_token(&ls_token_amount, &pool).unwrap();
+        assert!(released <= amount(delegated));
+    }
+}
+
+// Small xorshift generator, every run replays the same sequences
+struct Prng(u64);
+
+impl Prng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    // Spreads amounts over many magnitudes, from single units to 18 decimals tokens
+    fn amount(&mut self) -> u64 {
+        let magnitude = self.below(19) as u32;
+        1 + self.below(10u64.pow(magnitude))
+    }
+}
+
+fn big(value: u64) -> num_bigint::BigUint {
+    num_bigint::BigUint::from(value)
+}
+
+// The price of one LS token, as the reserve over supply fraction, never decreases through delegations, redemptions or sweeps
+fn assert_price_not_decreased(before: &OffchainPool, after: &OffchainPool) {
+    if before.ls_token_supply == num_bigint::BigUint::ZERO
+        || after.ls_token_supply == num_bigint::BigUint::ZERO
+    {
+        return;
+    }
+
+    assert!(
+        &after.virtual_xoxno_reserve * &before.ls_token_supply
+            >= &before.virtual_xoxno_reserve * &after.ls_token_supply
+    );
+}
+
+#[test]
+fn test_no_operation_sequence_extracts_value() {
+    const USERS: usize = 4;
+
+    let mut prng = Prng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..200 {
+        let mut pool = OffchainPool::default();
+        let mut ls_token_balances = [num_bigint::BigUint::ZERO; USERS];
+        let mut deposited = num_bigint::BigUint::ZERO;
+        let mut rewarded = num_bigint::BigUint::ZERO;
+        let mut paid_out = num_bigint::BigUint::ZERO;
+        let mut swept = num_bigint::BigUint::ZERO;
+
+        for _ in 0..50 {
+            let before = pool.clone();
+            let user = prng.below(USERS as u64) as usize;
+            match prng.below(5) {
+                0 => {
+                    let xoxno_amount = big(prng.amount());
+                    if let Ok(ls_token_amount) = pool.delegate(&xoxno_amount) {
+                        ls_token_balances[user] += ls_token_amount;
+                        deposited += xoxno_amount;
+                    }
+                    assert_price_not_decreased(&before, &pool);
+                }
+                1 => {
+                    let ls_token_amount = &ls_token_balances[user] * big(prng.below(101)) / 100u32;
+                    let tier_fee = prng.below(2) * prng.below(1_001);
+                    if let Ok(undelegation) = pool.undelegate(&ls_token_amount, tier_fee) {
+                        ls_token_balances[user] -= &ls_token_amount;
+                        paid_out += undelegation.xoxno_to_unstake;
+                    }
+                    assert_price_not_decreased(&before, &pool);
+                }
+                2 => {
+                    // Delegating and redeeming right away never returns more than was delegated
+                    let xoxno_amount = big(prng.amount());
+                    if let Ok(ls_token_amount) = pool.delegate(&xoxno_amount) {
+                        deposited += &xoxno_amount;
+                        match pool.undelegate(&ls_token_amount, 0) {
+                            Ok(undelegation) => {
+                                assert!(undelegation.xoxno_to_unstake <= xoxno_amount);
+                                paid_out += undelegation.xoxno_to_unstake;
+                            }
+                            Err(_) => ls_token_balances[user] += ls_token_amount,
+                        }
+                    }
+                    assert_price_not_decreased(&before, &pool);
+                }
+                3 => {
+                    let rewards_amount = big(prng.amount());
+                    pool.add_rewards(&rewards_amount);
+                    rewarded += rewards_amount;
+                }
+                _ => {
+                    if let Ok(dust_amount) = pool.sweep_rounding_dust() {
+                        swept += dust_amount;
+                    }
+                    assert_eq!(pool.exchange_rate(), before.exchange_rate());
+                }
+            }
+
+            // Every XOXNO unit that came in is either in the reserve, in the dust or was paid out exactly once
+            assert_eq!(
+                &pool.virtual_xoxno_reserve + &pool.rounding_dust + &paid_out + &swept,
+                &deposited + &rewarded
+            );
+            let total_ls_token_balance: num_bigint::BigUint = ls_token_balances.iter().sum();
+            assert_eq!(total_ls_token_balance, pool.ls_token_supply);
+        }
+
+        // Everyone can still leave, the last one takes what is left of the reserve but never the dust
+        for ls_token_balance in ls_token_balances.iter_mut() {
+            if *ls_token_balance == num_bigint::BigUint::ZERO {
+                continue;
+            }
+            let before = pool.clone();
+            if let Ok(undelegation) = pool.undelegate(ls_token_balance, 0) {
+                paid_out += undelegation.xoxno_to_unstake;
+                *ls_token_balance = num_bigint::BigUint::ZERO;
+            }
+            assert_price_not_decreased(&before, &pool);
+        }
+        assert!(paid_out <= &deposited + &rewarded - &swept - &pool.rounding_dust);
+    }
+}
+
+#[test]
+fn test_pricing_matches_offchain_mirror() {
+    let mut prng = Prng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2_000 {
+        let ls_token_supply = prng.below(4) * prng.amount();
+        let virtual_xoxno_reserve = prng.below(4) * prng.amount();
+        let probe = prng.below(8) * prng.amount();
+
+        let managed_pool = pool(ls_token_supply, virtual_xoxno_reserve);
+        let offchain_pool = OffchainPool::new(big(ls_token_supply), big(virtual_xoxno_reserve));
+        let to_big = |amount: BigUint<StaticApi>| {
+            num_bigint::BigUint::from_bytes_be(amount.to_bytes_be().as_slice())
+        };
+
+        assert_eq!(
+            pricing::ls_token_amount_for_xoxno(&amount(probe), &managed_pool).map(to_big),
+            offchain_pool.ls_token_amount_for_xoxno(&big(probe))
+        );
+        assert_eq!(
+            pricing::xoxno_amount_for_ls_token(&amount(probe), &managed_pool).map(to_big),
+            offchain_pool.xoxno_amount_for_ls_token(&big(probe))
+        );
+        assert_eq!(
+            to_big(pricing::exchange_rate(&managed_pool)),
+            offchain_pool.exchange_rate()
+        );
+        let decimals = prng.below(37) as u32;
+        assert_eq!(
+            to_big(pricing::exchange_rate_with_precision(
+                &managed_pool,
+                decimals
+            )),
+            offchain_pool.exchange_rate_with_precision(decimals)
+        );
+        if let Ok(ls_token_amount) = offchain_pool.ls_token_amount_for_xoxno(&big(probe)) {
+            let managed_ls_token_amount =
+                BigUint::<StaticApi>::from_bytes_be(&ls_token_amount.to_bytes_be());
+            assert_eq!(
+                to_big(pricing::delegation_dust(
+                    &amount(probe),
+                    &managed_ls_token_amount,
+                    &managed_pool
+                )),
+                offchain_pool.delegation_dust(&big(probe), &ls_token_amount)
+            );
+        }
+
+        let mut delegated_pool = managed_pool.clone();
+        let mut offchain_delegated_pool = offchain_pool.clone();
+        let delegation = pricing::apply_delegation(&amount(probe), &mut delegated_pool);
+        assert_eq!(
+            delegation
+                .map(|delegation| to_big(delegation.ls_token_amount))
+                .map_err(|error| error.to_vec()),
+            offchain_delegated_pool
+                .delegate(&big(probe))
+                .map_err(|error| error.to_vec())
+        );
+        assert_eq!(
+            to_big(delegated_pool.virtual_xoxno_reserve),
+            offchain_delegated_pool.virtual_xoxno_reserve
+        );
+
+        let tier_fee = prng.below(10_001);
+        let mut undelegated_pool = managed_pool.clone();
+        let mut offchain_undelegated_pool = offchain_pool.clone();
+        let undelegation =
+            pricing::apply_undelegation(&amount(probe), tier_fee, &mut undelegated_pool);
+        assert_eq!(
+            undelegation
+                .map(|undelegation| to_big(undelegation.xoxno_to_unstake))
+                .map_err(|error| error.to_vec()),
+            offchain_undelegated_pool
+                .undelegate(&big(probe), tier_fee)
+                .map(|undelegation| undelegation.xoxno_to_unstake)
+                .map_err(|error| error.to_vec())
+        );
+        assert_eq!(
+            to_big(undelegated_pool.virtual_xoxno_reserve),
+            offchain_undelegated_pool.virtual_xoxno_reserve
+        );
+    }
+}
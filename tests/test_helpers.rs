@@ -1,9 +1,15 @@
 use multiversx_sc_scenario::imports::*;
+use multiversx_sc_scenario::scenario_model::{
+    BytesKey, BytesValue, CheckStorage, CheckStorageDetails, CheckValue, Log,
+};
+use rs_liquid_xoxno::offchain::OffchainPool;
 use rs_liquid_xoxno::*;
 use rs_xoxno_proxy::*;
+use std::collections::BTreeMap;
 
 pub const OWNER_ADDRESS: TestAddress = TestAddress::new("owner");
 pub const DELEGATOR_ADDRESS: TestAddress = TestAddress::new("delegator");
+pub const RECIPIENT_ADDRESS: TestAddress = TestAddress::new("recipient");
 pub const RS_LIQUIDXOXNO_ADDRESS: TestSCAddress = TestSCAddress::new("rs_liquid_xoxno");
 pub const CODE_PATH: MxscPath = MxscPath::new("output/rs-liquid-xoxno.mxsc.json");
 pub const MAIN_TOKEN_ID: TestTokenIdentifier = TestTokenIdentifier::new("XOXNO-123456");
@@ -16,10 +22,32 @@ pub fn init_world() -> ScenarioWorld {
     blockchain
 }
 
-pub fn deploy_contract(world: &mut ScenarioWorld) -> TestSCAddress {
+pub fn deploy_contract(world: &mut ScenarioWorld) -> TestSCAddress<'static> {
+    deploy_contract_with_unstake_token_mode(world, UnstakeTokenMode::PerPosition)
+}
+
+pub fn deploy_contract_with_unstake_token_mode(
+    world: &mut ScenarioWorld,
+    unstake_token_mode: UnstakeTokenMode,
+) -> TestSCAddress<'static> {
+    deploy_contract_with_raw_storage(world, unstake_token_mode, &[])
+}
+
+// Extra storage entries are written as given, without going through any encoding
+pub fn deploy_contract_with_raw_storage(
+    world: &mut ScenarioWorld,
+    unstake_token_mode: UnstakeTokenMode,
+    raw_storage: &[(&str, &[u8])],
+) -> TestSCAddress<'static> {
     let liquid_sc = world.code_expression(&CODE_PATH.eval_to_expr());
     let mut acc = Account::new().code(liquid_sc).owner(OWNER_ADDRESS);
 
+    // Contracts deployed before the unstake token mode existed only have per-position tokens
+    if unstake_token_mode == UnstakeTokenMode::PerEpoch {
+        acc.storage
+            .insert(b"unstakeTokenMode".to_vec().into(), vec![1u8].into());
+    }
+
     acc.storage.insert(
         b"lsTokenId".to_vec().into(),
         b"LXOXNO-123456".to_vec().into(),
@@ -35,6 +63,11 @@ pub fn deploy_contract(world: &mut ScenarioWorld) -> TestSCAddress {
         b"UXOXNO-123456".to_vec().into(),
     );
 
+    for (key, value) in raw_storage {
+        acc.storage
+            .insert(key.as_bytes().to_vec().into(), value.to_vec().into());
+    }
+
     world.set_state_step(
         SetStateStep::new()
             .put_account(RS_LIQUIDXOXNO_ADDRESS, acc)
@@ -56,11 +89,23 @@ pub fn deploy_contract(world: &mut ScenarioWorld) -> TestSCAddress {
             EsdtLocalRole::NftBurn,
             EsdtLocalRole::NftCreate,
             EsdtLocalRole::NftAddUri,
+            EsdtLocalRole::NftAddQuantity,
         ],
     );
     RS_LIQUIDXOXNO_ADDRESS
 }
 
+pub fn upgrade_contract(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .upgrade()
+        .code(CODE_PATH)
+        .run();
+}
+
 pub fn set_contract_state(world: &mut ScenarioWorld, state: State) {
     match state {
         State::Active => {
@@ -81,6 +126,15 @@ pub fn set_contract_state(world: &mut ScenarioWorld, state: State) {
                 .set_state_inactive()
                 .run();
         }
+        State::Emergency => {
+            world
+                .tx()
+                .from(OWNER_ADDRESS)
+                .to(RS_LIQUIDXOXNO_ADDRESS)
+                .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+                .enable_emergency_mode()
+                .run();
+        }
     }
 }
 
@@ -99,18 +153,195 @@ pub fn add_liquidity(world: &mut ScenarioWorld, token_amount: u64) {
         .run();
 }
 
+pub fn add_liquidity_expect_error(world: &mut ScenarioWorld, token_amount: u64, message: &str) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate(multiversx_sc::proxy_imports::OptionalValue::<TestAddress>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(token_amount),
+        )
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn add_liquidity_multi(world: &mut ScenarioWorld, token_amounts: &[u64]) {
+    let mut payments = MultiEsdtPayment::new();
+    for amount in token_amounts {
+        payments.push(TestEsdtTransfer(MAIN_TOKEN_ID, 0, *amount).into());
+    }
+
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate(multiversx_sc::proxy_imports::OptionalValue::<TestAddress>::None)
+        .payment(payments)
+        .run();
+}
+
+pub fn add_liquidity_split(
+    world: &mut ScenarioWorld,
+    token_amount: u64,
+    recipients: &[(TestAddress, u64)],
+) {
+    let mut args = MultiValueEncoded::new();
+    for (recipient, weight) in recipients {
+        args.push(MultiValue2::from((recipient.to_managed_address(), *weight)));
+    }
+
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .delegate_split(args)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(token_amount),
+        )
+        .run();
+}
+
 pub fn remove_liquidity(world: &mut ScenarioWorld, token_amount: u64) {
     world
         .tx()
         .from(DELEGATOR_ADDRESS)
         .to(RS_LIQUIDXOXNO_ADDRESS)
         .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
-        .un_delegate()
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::<u8>::None)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(token_amount),
+        )
+        .run();
+}
+
+pub fn remove_liquidity_expect_error(world: &mut ScenarioWorld, token_amount: u64, message: &str) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::<u8>::None)
         .egld_or_single_esdt(
             &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
             0u64,
             &multiversx_sc::proxy_imports::BigUint::from(token_amount),
         )
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn remove_liquidity_with_tier(world: &mut ScenarioWorld, token_amount: u64, tier_id: u8) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .un_delegate(multiversx_sc::proxy_imports::OptionalValue::Some(tier_id))
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(LS_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(token_amount),
+        )
+        .run();
+}
+
+pub fn set_unbond_tier(world: &mut ScenarioWorld, tier_id: u8, unbond_epochs: u64, fee: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unbond_tier(tier_id, unbond_epochs, fee)
+        .run();
+}
+
+pub fn set_max_unbond_per_epoch(world: &mut ScenarioWorld, max_amount: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_max_unbond_per_epoch(max_amount)
+        .run();
+}
+
+pub fn set_max_total_reserve(world: &mut ScenarioWorld, max_amount: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_max_total_reserve(max_amount)
+        .run();
+}
+
+pub fn set_max_delegation_per_address(world: &mut ScenarioWorld, max_amount: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_max_delegation_per_address(max_amount)
+        .run();
+}
+
+pub fn set_minimum_amounts(
+    world: &mut ScenarioWorld,
+    min_delegate: u64,
+    min_undelegate: u64,
+    instant_withdraw_threshold: u64,
+) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_min_delegate_amount(min_delegate)
+        .run();
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_min_undelegate_amount(min_undelegate)
+        .run();
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_instant_withdraw_threshold(instant_withdraw_threshold)
+        .run();
+}
+
+pub fn apply_loss(world: &mut ScenarioWorld, loss_amount: u64, include_pending: bool) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .apply_loss(loss_amount, include_pending)
+        .run();
+}
+
+pub fn emergency_redeem(world: &mut ScenarioWorld, payment: TestEsdtTransfer) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .emergency_redeem()
+        .esdt(payment)
         .run();
 }
 
@@ -141,13 +372,106 @@ pub fn add_rewards(world: &mut ScenarioWorld, token_amount: u64) {
         .run();
 }
 
+pub fn add_airdrop_entries(world: &mut ScenarioWorld, entries: &[(TestAddress, u64)]) {
+    let mut args = MultiValueEncoded::new();
+    let mut total_amount = 0u64;
+    for (beneficiary, amount) in entries {
+        args.push(MultiValue2::from((
+            beneficiary.to_managed_address(),
+            multiversx_sc::proxy_imports::BigUint::from(*amount),
+        )));
+        total_amount += amount;
+    }
+
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .add_airdrop_entries(args)
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(MAIN_TOKEN_ID),
+            0u64,
+            &multiversx_sc::proxy_imports::BigUint::from(total_amount),
+        )
+        .run();
+}
+
+pub fn process_airdrop(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .process_airdrop()
+        .returns(ExpectValue(OperationCompletionStatus::Completed))
+        .run();
+}
+
+pub fn cancel_airdrop(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .cancel_airdrop()
+        .returns(ExpectValue(OperationCompletionStatus::Completed))
+        .run();
+}
+
+pub fn check_airdrop_progress(
+    world: &mut ScenarioWorld,
+    remaining_entries: usize,
+    pending_amount: u64,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_airdrop_progress()
+        .returns(ExpectValue(MultiValue2::from((
+            remaining_entries,
+            multiversx_sc::proxy_imports::BigUint::from(pending_amount),
+        ))))
+        .run();
+}
+
 pub fn withdraw_nft(world: &mut ScenarioWorld, nonce: u64) {
+    withdraw_unstake_tokens(world, nonce, 1u64);
+}
+
+pub fn withdraw_unstake_tokens(world: &mut ScenarioWorld, nonce: u64, amount: u64) {
     world
         .tx()
         .from(DELEGATOR_ADDRESS)
         .to(RS_LIQUIDXOXNO_ADDRESS)
         .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
         .withdraw()
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
+            nonce,
+            &multiversx_sc::proxy_imports::BigUint::from(amount),
+        )
+        .run();
+}
+
+pub fn set_early_withdraw_max_penalty(world: &mut ScenarioWorld, max_penalty: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_early_withdraw_max_penalty(max_penalty)
+        .run();
+}
+
+pub fn early_withdraw_nft(world: &mut ScenarioWorld, nonce: u64) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .early_withdraw()
         .egld_or_single_esdt(
             &EgldOrEsdtTokenIdentifier::esdt(UNBOUND_TOKEN_ID),
             nonce,
@@ -156,6 +480,16 @@ pub fn withdraw_nft(world: &mut ScenarioWorld, nonce: u64) {
         .run();
 }
 
+pub fn check_early_withdraw_penalty(world: &mut ScenarioWorld, nonce: u64, expected_amount: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_early_withdraw_penalty(nonce, OptionalValue::<BigUint<StaticApi>>::None)
+        .returns(ExpectValue(expected_amount))
+        .run();
+}
+
 pub fn check_ls_token_supply(world: &mut ScenarioWorld, expected_amount: u64) {
     world
         .query()
@@ -186,33 +520,643 @@ pub fn check_unstake_token_supply(world: &mut ScenarioWorld, expected_amount: u6
         .run();
 }
 
-pub fn check_balance(
-    world: &mut ScenarioWorld,
-    address: TestAddress,
-    token_id: TestTokenIdentifier,
-    expected_balance: u64,
-) {
-    world
-        .check_account(address)
-        .esdt_balance(token_id, expected_balance);
-}
-
-pub fn check_contract_state(world: &mut ScenarioWorld, expected_state: State) {
+pub fn check_outstanding_unstake_amount(world: &mut ScenarioWorld, expected_amount: u64) {
     world
         .query()
         .to(RS_LIQUIDXOXNO_ADDRESS)
         .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
-        .state()
-        .returns(ExpectValue(expected_state))
+        .outstanding_unstake_amount()
+        .returns(ExpectValue(expected_amount))
         .run();
 }
 
-pub fn check_main_token(world: &mut ScenarioWorld, expected_token_id: TestTokenIdentifier) {
+pub fn set_unstake_token_id(
+    world: &mut ScenarioWorld,
+    token_id: TestTokenIdentifier,
+    unstake_token_mode: UnstakeTokenMode,
+) {
     world
-        .query()
+        .tx()
+        .from(OWNER_ADDRESS)
         .to(RS_LIQUIDXOXNO_ADDRESS)
         .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
-        .main_token()
-        .returns(ExpectValue(expected_token_id))
+        .set_unstake_token_id(token_id, OptionalValue::Some(unstake_token_mode))
+        .run();
+}
+
+pub fn set_unstake_token_id_expect_error(
+    world: &mut ScenarioWorld,
+    token_id: TestTokenIdentifier,
+    unstake_token_mode: UnstakeTokenMode,
+    message: &str,
+) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unstake_token_id(token_id, OptionalValue::Some(unstake_token_mode))
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn migrate_ls_token_expect_error(
+    world: &mut ScenarioWorld,
+    token_id: TestTokenIdentifier,
+    amount: u64,
+    message: &str,
+) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .migrate_ls_token()
+        .payment(TestEsdtTransfer(token_id, 0, amount))
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn check_remaining_total_capacity(world: &mut ScenarioWorld, expected_amount: Option<u64>) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_remaining_total_capacity()
+        .returns(ExpectValue(OptionalValue::from(
+            expected_amount.map(multiversx_sc::proxy_imports::BigUint::from),
+        )))
+        .run();
+}
+
+pub fn check_remaining_address_capacity(
+    world: &mut ScenarioWorld,
+    address: TestAddress,
+    expected_amount: Option<u64>,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_remaining_address_capacity(address)
+        .returns(ExpectValue(OptionalValue::from(
+            expected_amount.map(multiversx_sc::proxy_imports::BigUint::from),
+        )))
+        .run();
+}
+
+pub fn check_exchange_rate(world: &mut ScenarioWorld, expected_rate: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_exchange_rate()
+        .returns(ExpectValue(expected_rate))
+        .run();
+}
+
+pub fn check_balance(
+    world: &mut ScenarioWorld,
+    address: TestAddress,
+    token_id: TestTokenIdentifier,
+    expected_balance: u64,
+) {
+    world
+        .check_account(address)
+        .esdt_balance(token_id, expected_balance);
+}
+
+pub fn check_contract_state(world: &mut ScenarioWorld, expected_state: State) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .state()
+        .returns(ExpectValue(expected_state))
+        .run();
+}
+
+pub fn check_main_token(world: &mut ScenarioWorld, expected_token_id: TestTokenIdentifier) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .main_token()
+        .returns(ExpectValue(expected_token_id))
+        .run();
+}
+
+pub fn check_remaining_unbond_capacity(
+    world: &mut ScenarioWorld,
+    epoch: u64,
+    expected_amount: u64,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_remaining_unbond_capacity(epoch)
+        .returns(ExpectValue(OptionalValue::Some(
+            multiversx_sc::proxy_imports::BigUint::from(expected_amount),
+        )))
+        .run();
+}
+
+pub fn check_unbond_queue_depth(world: &mut ScenarioWorld, expected_depth: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unbond_queue_depth(OptionalValue::<u8>::None)
+        .returns(ExpectValue(expected_depth))
+        .run();
+}
+
+pub fn migrate_storage(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .migrate_storage()
+        .returns(ExpectValue(OperationCompletionStatus::Completed))
+        .run();
+}
+
+pub fn check_storage_migration_cursor(world: &mut ScenarioWorld, expected_cursor: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .storage_migration_cursor()
+        .returns(ExpectValue(expected_cursor))
+        .run();
+}
+
+pub fn check_storage_version(world: &mut ScenarioWorld, expected_version: u32) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .storage_version()
+        .returns(ExpectValue(expected_version))
+        .run();
+}
+
+pub fn propose_owner(world: &mut ScenarioWorld, new_owner: TestAddress) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .propose_owner(new_owner)
+        .run();
+}
+
+pub fn accept_ownership(world: &mut ScenarioWorld, caller: TestAddress) -> Vec<Log> {
+    world
+        .tx()
+        .from(caller)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .accept_ownership()
+        .returns(ReturnsLogs)
+        .run()
+}
+
+pub fn accept_ownership_expect_error(
+    world: &mut ScenarioWorld,
+    caller: TestAddress,
+    message: &str,
+) {
+    world
+        .tx()
+        .from(caller)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .accept_ownership()
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn cancel_ownership_proposal(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .cancel_ownership_proposal()
+        .run();
+}
+
+pub fn check_contract_owner(world: &mut ScenarioWorld, expected_owner: TestAddress) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_contract_owner()
+        .returns(ExpectValue(expected_owner))
+        .run();
+}
+
+pub fn set_unstake_token_uris(world: &mut ScenarioWorld, uris: &[&str]) {
+    let mut uri_args = MultiValueEncoded::new();
+    for uri in uris {
+        uri_args.push(ManagedBuffer::<StaticApi>::from(*uri));
+    }
+
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unstake_token_uris(uri_args)
+        .run();
+}
+
+pub fn set_unstake_token_name_template(world: &mut ScenarioWorld, template: &str) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_unstake_token_name_template(template)
+        .run();
+}
+
+pub fn check_unstake_token_uris(world: &mut ScenarioWorld, expected_uris: &[&str]) {
+    let uris = world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unstake_token_uris()
+        .returns(ReturnsResult)
+        .run();
+
+    assert_eq!(uris.len(), expected_uris.len());
+    for (uri, expected_uri) in uris.iter().zip(expected_uris) {
+        assert_eq!(*uri, ManagedBuffer::from(*expected_uri));
+    }
+}
+
+pub fn check_unstake_token_metadata(
+    world: &mut ScenarioWorld,
+    nonce: u64,
+    expected_metadata: &str,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unstake_token_metadata(nonce)
+        .returns(ExpectValue(ManagedBuffer::<StaticApi>::from(
+            expected_metadata,
+        )))
+        .run();
+}
+
+pub fn check_epoch_unstake_nonce(
+    world: &mut ScenarioWorld,
+    unbond_epoch: u64,
+    unstake_epoch: u64,
+    tier_id: u8,
+    expected_nonce: u64,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_epoch_unstake_nonce(unbond_epoch, unstake_epoch, tier_id)
+        .returns(ExpectValue(expected_nonce))
+        .run();
+}
+
+pub fn check_epoch_unstake_nonces(
+    world: &mut ScenarioWorld,
+    unbond_epoch: u64,
+    expected_nonces: &[u64],
+) {
+    let nonces = world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_epoch_unstake_nonces(unbond_epoch)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(nonces.into_vec(), expected_nonces.to_vec());
+}
+
+pub fn set_ls_token_id(world: &mut ScenarioWorld, token_id: TestTokenIdentifier) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_ls_token_id(token_id)
+        .run();
+}
+
+pub fn migrate_ls_token(world: &mut ScenarioWorld, token_id: TestTokenIdentifier, amount: u64) {
+    world
+        .tx()
+        .from(DELEGATOR_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .migrate_ls_token()
+        .payment(TestEsdtTransfer(token_id, 0, amount))
+        .run();
+}
+
+pub fn check_missing_token_roles(world: &mut ScenarioWorld, expected_roles: &[(&str, &str)]) {
+    let missing_roles = world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_missing_token_roles()
+        .returns(ReturnsResult)
+        .run();
+
+    let missing_roles: Vec<_> = missing_roles
+        .into_iter()
+        .map(|role| {
+            let (token_id, role_name) = role.into_tuple();
+            (token_id.to_string(), role_name.to_string())
+        })
+        .collect();
+    let expected_roles: Vec<_> = expected_roles
+        .iter()
+        .map(|(token_id, role_name)| (token_id.to_string(), role_name.to_string()))
+        .collect();
+    assert_eq!(missing_roles, expected_roles);
+}
+
+// Compares the raw bytes stored under the key, leaving every other key unchecked
+pub fn check_raw_storage(world: &mut ScenarioWorld, key: &str, expected_value: &[u8]) {
+    let mut storages = BTreeMap::new();
+    storages.insert(
+        BytesKey::from(key.as_bytes().to_vec()),
+        CheckValue::Equal(BytesValue::from(expected_value.to_vec())),
+    );
+    world.check_state_step(CheckStateStep::new().put_account(
+        RS_LIQUIDXOXNO_ADDRESS,
+        CheckAccount {
+            storage: CheckStorage::Equal(CheckStorageDetails {
+                storages,
+                other_storages_allowed: true,
+            }),
+            ..Default::default()
+        },
+    ));
+}
+
+pub fn check_exchange_rate_fraction(
+    world: &mut ScenarioWorld,
+    expected_reserve: u64,
+    expected_supply: u64,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_exchange_rate_fraction()
+        .returns(ExpectValue(MultiValue2::from((
+            multiversx_sc::proxy_imports::BigUint::from(expected_reserve),
+            multiversx_sc::proxy_imports::BigUint::from(expected_supply),
+        ))))
+        .run();
+}
+
+pub fn check_exchange_rate_with_precision(
+    world: &mut ScenarioWorld,
+    decimals: u32,
+    expected_rate: u64,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_exchange_rate_with_precision(decimals)
+        .returns(ExpectValue(expected_rate))
+        .run();
+}
+
+pub fn get_contract_summary(world: &mut ScenarioWorld) -> ContractSummary<StaticApi> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_contract_summary()
+        .returns(ReturnsResult)
+        .run()
+}
+
+pub fn set_treasury(world: &mut ScenarioWorld, treasury: TestAddress) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .set_treasury(treasury)
+        .run();
+}
+
+pub fn sweep_rounding_dust(world: &mut ScenarioWorld, expected_amount: u64) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .sweep_rounding_dust()
+        .returns(ExpectValue(expected_amount))
+        .run();
+}
+
+pub fn sweep_rounding_dust_expect_error(world: &mut ScenarioWorld, message: &str) {
+    world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .sweep_rounding_dust()
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn check_rounding_dust(world: &mut ScenarioWorld, expected_amount: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .rounding_dust()
+        .returns(ExpectValue(expected_amount))
+        .run();
+}
+
+// Compares the pool amounts and the quote views of the contract with the off-chain mirror
+pub fn check_offchain_pool(world: &mut ScenarioWorld, pool: &OffchainPool) {
+    let to_u64 = |amount: &num_bigint::BigUint| u64::try_from(amount).unwrap();
+    check_ls_token_supply(world, to_u64(&pool.ls_token_supply));
+    check_virtual_xoxno_reserve(world, to_u64(&pool.virtual_xoxno_reserve));
+    check_exchange_rate(world, to_u64(&pool.exchange_rate()));
+    let (reserve, supply) = pool.exchange_rate_fraction();
+    check_exchange_rate_fraction(world, to_u64(&reserve), to_u64(&supply));
+    check_rounding_dust(world, to_u64(&pool.rounding_dust));
+
+    for probe in [0u64, 1, 2, 3, 7, 100, 999, 1_000_000] {
+        let probe_amount = num_bigint::BigUint::from(probe);
+
+        let query = world
+            .query()
+            .to(RS_LIQUIDXOXNO_ADDRESS)
+            .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+            .get_ls_amount_for_position(probe);
+        match pool.ls_token_amount_for_xoxno(&probe_amount) {
+            Ok(expected) => query.returns(ExpectValue(to_u64(&expected))).run(),
+            Err(message) => query
+                .returns(ExpectError(4, core::str::from_utf8(message).unwrap()))
+                .run(),
+        }
+
+        let query = world
+            .query()
+            .to(RS_LIQUIDXOXNO_ADDRESS)
+            .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+            .get_ls_value_for_position(probe);
+        match pool.xoxno_amount_for_ls_token(&probe_amount) {
+            Ok(expected) => query.returns(ExpectValue(to_u64(&expected))).run(),
+            Err(message) => query
+                .returns(ExpectError(4, core::str::from_utf8(message).unwrap()))
+                .run(),
+        }
+    }
+}
+
+pub fn quote(
+    output_amount: u64,
+    fee_amount: u64,
+    exchange_rate: u64,
+    unbond_epoch: u64,
+    error: QuoteError,
+) -> Quote<StaticApi> {
+    Quote {
+        output_amount: BigUint::from(output_amount),
+        fee_amount: BigUint::from(fee_amount),
+        exchange_rate: BigUint::from(exchange_rate),
+        unbond_epoch,
+        error,
+    }
+}
+
+pub fn quote_delegate(
+    world: &mut ScenarioWorld,
+    amount: u64,
+    delegator: Option<TestAddress>,
+) -> Quote<StaticApi> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .quote_delegate(amount, OptionalValue::from(delegator))
+        .returns(ReturnsResult)
+        .run()
+}
+
+pub fn quote_un_delegate(
+    world: &mut ScenarioWorld,
+    ls_token_amount: u64,
+    tier_id: Option<u8>,
+) -> Quote<StaticApi> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .quote_un_delegate(ls_token_amount, OptionalValue::from(tier_id))
+        .returns(ReturnsResult)
+        .run()
+}
+
+// Unstake token nonces with the quantity of each
+fn encode_unstake_positions(
+    positions: &[(u64, u64)],
+) -> MultiValueEncoded<StaticApi, MultiValue2<u64, BigUint<StaticApi>>> {
+    let mut encoded_positions = MultiValueEncoded::new();
+    for (nonce, amount) in positions {
+        encoded_positions.push((*nonce, BigUint::from(*amount)).into());
+    }
+    encoded_positions
+}
+
+pub fn quote_withdraw(world: &mut ScenarioWorld, positions: &[(u64, u64)]) -> Quote<StaticApi> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .quote_withdraw(encode_unstake_positions(positions))
+        .returns(ReturnsResult)
+        .run()
+}
+
+pub fn get_unstake_position_info(
+    world: &mut ScenarioWorld,
+    positions: &[(u64, u64)],
+) -> Vec<UnstakePositionInfo<StaticApi>> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unstake_position_info(encode_unstake_positions(positions))
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect()
+}
+
+pub fn get_unstake_position_info_expect_error(
+    world: &mut ScenarioWorld,
+    positions: &[(u64, u64)],
+    message: &str,
+) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_unstake_position_info(encode_unstake_positions(positions))
+        .returns(ExpectError(4, message))
+        .run();
+}
+
+pub fn get_protocol_stats(world: &mut ScenarioWorld) -> ProtocolStats<StaticApi> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_protocol_stats()
+        .returns(ReturnsResult)
+        .run()
+}
+
+pub fn get_epoch_reports(
+    world: &mut ScenarioWorld,
+    from_index: u64,
+    max_reports: u64,
+) -> Vec<EpochReport<StaticApi>> {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .get_epoch_reports(from_index, max_reports)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect()
+}
+
+pub fn check_epoch_report_count(world: &mut ScenarioWorld, expected_count: u64) {
+    world
+        .query()
+        .to(RS_LIQUIDXOXNO_ADDRESS)
+        .typed(rs_xoxno_proxy::RsLiquidXoxnoProxy)
+        .epoch_report_count()
+        .returns(ExpectValue(expected_count))
         .run();
 }
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           18
+// Endpoints:                           93
 // Async Callback:                       1
-// Total number of exported functions:  21
+// Total number of exported functions:  96
 
 #![no_std]
 
@@ -21,16 +21,50 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         delegate => delegate
+        delegateSplit => delegate_split
         unDelegate => un_delegate
         withdraw => withdraw
+        earlyWithdraw => early_withdraw
         addRewards => add_rewards
+        applyLoss => apply_loss
+        sweepRoundingDust => sweep_rounding_dust
         getMainTokenAmountForPosition => get_ls_value_for_position
         getLsTokenAmountForMainTokenAmount => get_ls_amount_for_position
+        getEarlyWithdrawPenalty => get_early_withdraw_penalty
+        getUnstakeTokenMetadata => get_unstake_token_metadata
+        getUnstakePositionInfo => get_unstake_position_info
+        getRemainingTotalCapacity => get_remaining_total_capacity
+        getRemainingAddressCapacity => get_remaining_address_capacity
+        getRemainingUnbondCapacity => get_remaining_unbond_capacity
+        getUnbondQueueDepth => get_unbond_queue_depth
         getExchangeRate => get_exchange_rate
+        getContractSummary => get_contract_summary
+        getExchangeRateFraction => get_exchange_rate_fraction
+        getExchangeRateWithPrecision => get_exchange_rate_with_precision
         registerLsToken => register_ls_token
         registerUnstakeToken => register_unstake_token
         setStateActive => set_state_active
         setStateInactive => set_state_inactive
+        setEarlyWithdrawMaxPenalty => set_early_withdraw_max_penalty
+        setUnbondTier => set_unbond_tier
+        removeUnbondTier => remove_unbond_tier
+        setMaxUnbondPerEpoch => set_max_unbond_per_epoch
+        setMaxTotalReserve => set_max_total_reserve
+        setMaxDelegationPerAddress => set_max_delegation_per_address
+        setMinDelegateAmount => set_min_delegate_amount
+        setMinUnDelegateAmount => set_min_undelegate_amount
+        setInstantWithdrawThreshold => set_instant_withdraw_threshold
+        setMaxLossPerCall => set_max_loss_per_call
+        setTreasury => set_treasury
+        setUnstakeTokenUris => set_unstake_token_uris
+        setUnstakeTokenNameTemplate => set_unstake_token_name_template
+        getUnstakeTokenUris => get_unstake_token_uris
+        getUnstakeTokenNameTemplate => get_unstake_token_name_template
+        getEpochUnstakeNonce => get_epoch_unstake_nonce
+        getEpochUnstakeNonces => get_epoch_unstake_nonces
+        getMaxLossPerCall => get_max_loss_per_call
+        getUnstakeValueIndex => get_unstake_value_index
+        getUnbondTiers => get_unbond_tiers
         getState => state
         getLsTokenId => ls_token
         getMainToken => main_token
@@ -38,6 +72,47 @@ multiversx_sc_wasm_adapter::endpoints! {
         getVirtualXOXNOReserve => virtual_xoxno_reserve
         getUnstakeTokenId => unstake_token
         getUnstakeTokenSupply => unstake_token_supply
+        getOutstandingUnstakeAmount => outstanding_unstake_amount
+        getEarlyWithdrawMaxPenalty => early_withdraw_max_penalty
+        getMaxUnbondPerEpoch => max_unbond_per_epoch
+        getScheduledUnbond => scheduled_unbond
+        getMaxTotalReserve => max_total_reserve
+        getMaxDelegationPerAddress => max_delegation_per_address
+        getDelegatedAmount => delegated_amount
+        getMinDelegateAmount => min_delegate_amount
+        getMinUnDelegateAmount => min_undelegate_amount
+        getInstantWithdrawThreshold => instant_withdraw_threshold
+        getUnstakeTokenMode => unstake_token_mode
+        getTreasury => treasury
+        getRoundingDust => rounding_dust
+        getProtocolStats => get_protocol_stats
+        getEpochReports => get_epoch_reports
+        getEpochReportCount => epoch_report_count
+        migrateStorage => migrate_storage
+        getStorageVersion => storage_version
+        getStorageMigrationCursor => storage_migration_cursor
+        addAirdropEntries => add_airdrop_entries
+        processAirdrop => process_airdrop
+        cancelAirdrop => cancel_airdrop
+        getAirdropProgress => get_airdrop_progress
+        getAirdropPendingAmount => airdrop_pending_amount
+        enableEmergencyMode => enable_emergency_mode
+        emergencyRedeem => emergency_redeem
+        getContractOwner => get_contract_owner
+        getPendingOwner => pending_owner
+        proposeOwner => propose_owner
+        acceptOwnership => accept_ownership
+        cancelOwnershipProposal => cancel_ownership_proposal
+        setLsTokenId => set_ls_token_id
+        setUnstakeTokenId => set_unstake_token_id
+        requestLsTokenRoles => request_ls_token_roles
+        requestUnstakeTokenRoles => request_unstake_token_roles
+        migrateLsToken => migrate_ls_token
+        getMissingTokenRoles => get_missing_token_roles
+        getLegacyLsTokenIds => legacy_ls_token_ids
+        quoteDelegate => quote_delegate
+        quoteUnDelegate => quote_un_delegate
+        quoteWithdraw => quote_withdraw
     )
 }
 